@@ -5,17 +5,17 @@ use vector::vector::Vector;
 
 
 fn render_vector(v: &Vector, color: &Color) {
-    draw_circle(v.0, v.1, 10., color.clone());
+    draw_circle(v.0, v.1, 10., *color);
 }
 
 
 fn render_segment(s: &Segment, color: &Color) {
     let end = s.end();
 
-    draw_line(s.location.0 , s.location.1, end.0, end.1, 2., color.clone());
-    draw_circle(s.location.0 , s.location.1, 10., color.clone());
+    draw_line(s.location.0 , s.location.1, end.0, end.1, 2., *color);
+    draw_circle(s.location.0 , s.location.1, 10., *color);
     
-    draw_circle(end.0, end.1, 10., color.clone());
+    draw_circle(end.0, end.1, 10., *color);
 }
 
 pub fn shape_project_point(x:f32, y:f32) {
@@ -39,17 +39,39 @@ pub fn shape_project_point(x:f32, y:f32) {
 fn render_shape(s:&Shape, color: &Color) {
     match s {
         Shape::Dot(d) => {
-            draw_circle(d.0 , d.1, 3., color.clone());
+            draw_circle(d.0 , d.1, 3., *color);
         }
         Shape::Circle(c) => {
-            draw_circle_lines(c.location.0 , c.location.1, c.radius, 2., color.clone());
+            draw_circle_lines(c.location.0 , c.location.1, c.radius, 2., *color);
         }
         Shape::Rectangle(r) => {
             let frame = r.to_frame();
-            draw_rectangle_lines(frame.from.0, frame.from.1, r.dementions.0, r.dementions.1, 2.0, color.clone());
+            draw_rectangle_lines(frame.from.0, frame.from.1, r.dementions.0, r.dementions.1, 2.0, *color);
 
         }
-        _ => {}
+        Shape::Polygon(p) => {
+            for (a, b) in p.edges() {
+                draw_line(a.0, a.1, b.0, b.1, 2., *color);
+            }
+        }
+        Shape::OrientedRectangle(r) => {
+            let corners = r.corners();
+            for i in 0..4 {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                draw_line(a.0, a.1, b.0, b.1, 2., *color);
+            }
+        }
+        Shape::Capsule(c) => {
+            let [a, b] = c.ends();
+            let side = c.segment.vector.perp_left().normalize().clone_scale(c.radius);
+            draw_circle_lines(a.0, a.1, c.radius, 2., *color);
+            draw_circle_lines(b.0, b.1, c.radius, 2., *color);
+            draw_line(a.0 + side.0, a.1 + side.1, b.0 + side.0, b.1 + side.1, 2., *color);
+            draw_line(a.0 - side.0, a.1 - side.1, b.0 - side.0, b.1 - side.1, 2., *color);
+        }
+        Shape::Segment(segment) => {
+            render_segment(segment, color);
+        }
     }
 }
 
@@ -75,11 +97,8 @@ pub fn shape_circles_collision(x:f32, y:f32) {
 
         let r = c1.collision_with(&c2);
 
-        match r {
-            Some(p) => {
-                render_vector(&p, &RED);
-            }
-            _ => {}
+        if let Some(p) = r {
+            render_vector(&p, &RED);
         }
 
 }
@@ -106,11 +125,8 @@ pub fn shape_rectangles_collision(x:f32, y:f32) {
 
         let r = r1.collision_with(&r2);
 
-        match r {
-            Some(p) => {
-                render_vector(&p, &RED);
-            }
-            _ => {}
+        if let Some(p) = r {
+            render_vector(&p, &RED);
         }
 
 }
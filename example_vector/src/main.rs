@@ -4,7 +4,7 @@ use vector::vector::Vector;
 
 
 fn render_vector(v: &Vector, color: &Color) {
-    draw_circle(v.1, v.0, 10., color.clone());
+    draw_circle(v.1, v.0, 10., *color);
     
 }
 #[macroquad::main("MyGame")]
@@ -19,7 +19,7 @@ async fn main() {
 
         let a = Vector(60., 100.);
         let b = Vector(y, x);
-        let c = a.clone_add(&b);
+        let c = a + b;
         render_vector(&a, &GREEN);
         render_vector(&b, &BLUE);
         render_vector(&c, &RED);
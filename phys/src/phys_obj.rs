@@ -1,27 +1,63 @@
 use range::range::Range;
 use shape::{circle::Circle, rectangle::Rectangle, shape::Shape};
+use shape::traits::{Bounded, Translate};
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
 use vector::vector::Vector;
 
-#[derive(PartialEq,Debug)]
-pub struct Collision {
-    pub location: Vector,
-    pub time_offset: f32,
+#[derive(PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Collision<T = f32> {
+    pub location: Vector<T>,
+    pub time_offset: T,
 } 
 
-pub struct PhysObj {
+/**
+ * Moving object. The shape is the Shape enum by default,
+ * any type implementing the shape traits can be used instead.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysObj<T = f32, S = Shape<T>> {
     pub id: Option<usize>,
-    pub shape: Shape,
-    pub velosity: Vector,
+    pub shape: S,
+    pub velosity: Vector<T>,
 }
 
+impl<T> ApproxEq for Collision<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
 
-impl  PhysObj {
+    fn approx_eq(&self, other: &Collision<T>, tolerance: Tolerance<T>) -> bool {
+        return self.location.approx_eq(&other.location, tolerance)
+            && self.time_offset.approx_eq(&other.time_offset, tolerance);
+    }
+}
+
+
+impl<T: Scalar, S: Translate<T>> PhysObj<T, S> {
+    pub fn shift(&mut self, time: T) {
+        self.shape.shift(&(self.velosity * time));
+    }
+}
 
-    pub fn shift(&mut self, time: f32) {
-        self.shape.shift(self.velosity.scale(time));
+impl<T: Scalar, S: Bounded<T>> PhysObj<T, S> {
+    /**
+     * When the bounding frames of the moving objects start to overlap,
+     * works for every shape type but is only exact for axis-aligned rectangles
+     */
+    pub fn predict_frame_collision<U: Bounded<T>>(a: &PhysObj<T, S>, b: &PhysObj<T, U>) -> Option<Collision<T>> {
+        let r1 = Rectangle::from_frame(&a.shape.to_frame());
+        let r2 = Rectangle::from_frame(&b.shape.to_frame());
+        return PhysObj::rect_vs_rect(&r1, &a.velosity, &r2, &b.velosity);
     }
+}
+
+impl<T: Scalar> PhysObj<T> {
 
-    pub fn  predict_collision(a: &PhysObj, b: &PhysObj) -> Option<Collision> {
+    pub fn  predict_collision(a: &PhysObj<T>, b: &PhysObj<T>) -> Option<Collision<T>> {
        match (&a.shape, &b.shape) {
             (Shape::Circle(c1), Shape::Circle(c2)) => {
                 return PhysObj::circle_vs_circle(c1, &a.velosity, c2, &b.velosity);
@@ -33,10 +69,10 @@ impl  PhysObj {
         }
     }
 
-    fn rect_vs_rect (r1: &Rectangle, v1: &Vector, r2: &Rectangle,v2: &Vector)  -> Option<Collision> {
-        let dp = r2.location.clone_sub(&r1.location);
-        let dv = v2.clone_sub(v1);
-        let d = r1.dementions.clone_add(&r2.dementions).scale(0.5).to_owned();
+    fn rect_vs_rect (r1: &Rectangle<T>, v1: &Vector<T>, r2: &Rectangle<T>,v2: &Vector<T>)  -> Option<Collision<T>> {
+        let dp = r2.location - r1.location;
+        let dv = *v2 - *v1;
+        let d = (r1.dementions + r2.dementions) * T::from_f32(0.5);
 
         // dist (dp + dv * t) = r
         // (dp + dv * t)^2 = r^2
@@ -44,8 +80,8 @@ impl  PhysObj {
         // t1 = (-r - dp) / dv
         // t2 = (r - dp) / dv
 
-        fn asix_collision_time(dp: f32, dv: f32, r: f32) -> Option<Range> {
-            if dv == 0. {
+        fn asix_collision_time<T: Scalar>(dp: T, dv: T, r: T) -> Option<Range<T>> {
+            if dv == T::ZERO {
                 Option::None
             } else {
                 let t1 = (-r - dp) / dv;
@@ -55,13 +91,13 @@ impl  PhysObj {
         }
 
         let cx = asix_collision_time(dp.0, dv.0, d.0);
-        let cy: Option<Range> = asix_collision_time(dp.1, dv.1, d.1);
+        let cy: Option<Range<T>> = asix_collision_time(dp.1, dv.1, d.1);
 
         if let (Some(range_x), Some(range_y)) = (cx, cy) {
                     if let Some(range_xy) = range_x.intercept(&range_y) {
                         let time_offset = range_xy.0;
-                        let moved1 = r1.clone_shift(&v1.clone_scale(time_offset)).to_frame();
-                        let moved2 = r2.clone_shift(&v2.clone_scale(time_offset)).to_frame();
+                        let moved1 = r1.clone_shift(&(*v1 * time_offset)).to_frame();
+                        let moved2 = r2.clone_shift(&(*v2 * time_offset)).to_frame();
 
                         if let Some(intr) = moved1.intercect(&moved2) {
                             let location = intr.center();
@@ -77,10 +113,10 @@ impl  PhysObj {
     }
 
     
-    fn circle_vs_circle (c1: &Circle, v1: &Vector, c2: &Circle,v2: &Vector)  -> Option<Collision> {
+    fn circle_vs_circle (c1: &Circle<T>, v1: &Vector<T>, c2: &Circle<T>,v2: &Vector<T>)  -> Option<Collision<T>> {
 
-        let dp = c2.location.clone_sub(&c1.location);
-        let dv = v2.clone_sub(v1);
+        let dp = c2.location - c1.location;
+        let dv = *v2 - *v1;
         let r = c1.radius + c2.radius;
 
         // dist (dp + dv * t) = r
@@ -95,24 +131,24 @@ impl  PhysObj {
         // C = (adp^2+bdp^2) - r^2
 
         let a = dv.0 * dv.0 + dv.1 * dv.1;
-        let b = 2. * (dp.0 * dv.1  + dp.1 * dv.0);
+        let b = T::from_f32(2.) * (dp.0 * dv.0 + dp.1 * dv.1);
         let c = dp.0 * dp.0 + dp.1 * dp.1 - r * r;
 
         let res = PhysObj::equasion(a, b, c);
 
         match res {
             Option::Some((t1,t2)) => {
-                let time_offset = if t1 >= 0.0 {
+                let time_offset = if t1 >= T::ZERO {
                     t1
-                } else if t2 >= 0.0 {
+                } else if t2 >= T::ZERO {
                     t2
                 } else  {
                     return None;
                 };
 
-                let new_c1 = c1.location.clone_add(&v1.clone_scale(time_offset));
-                let new_c2 = c2.location.clone_add(&v2.clone_scale(time_offset));
-                let location = new_c1.clone_add(&new_c2).clone_scale(0.5);
+                let new_c1 = c1.location + *v1 * time_offset;
+                let new_c2 = c2.location + *v2 * time_offset;
+                let location = (new_c1 + new_c2) * T::from_f32(0.5);
 
                 return Option::Some(
                     Collision{
@@ -127,16 +163,16 @@ impl  PhysObj {
 
 
     // t1 < t2
-    fn equasion (a:f32, b: f32, c: f32) -> Option<(f32, f32)> {
-        let d = b * b - 4.0 * a * c;
+    fn equasion (a:T, b: T, c: T) -> Option<(T, T)> {
+        let d = b * b - T::from_f32(4.0) * a * c;
 
-        if d < 0.0 || a.abs() < 1e-6 {
+        if d < T::ZERO || a == T::ZERO || a.abs() < T::from_f32(1e-6) {
             return Option::None;
         }
 
         let sqrt_disc = d.sqrt();
-        let t1 = (-b - sqrt_disc) / (2.0 * a);
-        let t2 = (-b + sqrt_disc) / (2.0 * a);
+        let t1 = (-b - sqrt_disc) / (T::from_f32(2.0) * a);
+        let t2 = (-b + sqrt_disc) / (T::from_f32(2.0) * a);
 
         return Option::Some((t1,t2))
 
@@ -149,10 +185,13 @@ impl  PhysObj {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shape::frame::Frame;
+    use vector::assert_approx_eq;
+    use vector::fixed::Fix16;
 
     #[test]
     fn circles_collision() {
-        let c1 =  PhysObj {
+        let c1: PhysObj =  PhysObj {
                 id: Option::None,
                 velosity: Vector(1.,1.),
                 shape: Shape::Circle(Circle {
@@ -185,13 +224,43 @@ mod tests {
         let y = PhysObj::predict_collision(&c1, &c3);
 
 
-        assert_eq!(x, Option::Some(Collision{location:Vector(5., 5.), time_offset: 4.2928934}));
-        assert_eq!(y, Option::Some(Collision{location:Vector(5., 6.), time_offset: 5.}));
+        assert_approx_eq!(x, Option::Some(Collision{location:Vector(5., 5.), time_offset: 4.2928932}), Tolerance::Absolute(1e-5));
+        assert_approx_eq!(y, Option::Some(Collision{location:Vector(5., 6.), time_offset: 5.}), Tolerance::Absolute(1e-5));
+    }
+
+    #[test]
+    fn circles_collision_off_diagonal() {
+        let c1: PhysObj = PhysObj {
+            id: Option::None,
+            velosity: Vector(1.3, 0.7),
+            shape: Shape::Circle(Circle {
+                location: Vector(0., 0.),
+                radius: 1.,
+            }),
+        };
+        let c2 = PhysObj {
+            id: Option::None,
+            velosity: Vector(-0.9, -1.1),
+            shape: Shape::Circle(Circle {
+                location: Vector(10., 9.),
+                radius: 1.5,
+            }),
+        };
+
+        // |dp + dv * t| = r with dp = (10, 9), dv = (-2.2, -1.8), r = 2.5
+        let (a, b, c) = (8.08f32, -76.4f32, 174.75f32);
+        let t = (-b - (b * b - 4. * a * c).sqrt()) / (2. * a);
+
+        let x = PhysObj::predict_collision(&c1, &c2).unwrap();
+        assert_approx_eq!(x.time_offset, t, Tolerance::Absolute(1e-4));
+        assert_approx_eq!(x.time_offset, 3.876907, Tolerance::Absolute(1e-4));
+        let expected = (Vector(1.3, 0.7) * t + Vector(10., 9.) + Vector(-0.9, -1.1) * t) * 0.5;
+        assert_approx_eq!(x.location, expected, Tolerance::Absolute(1e-4));
     }
 
     #[test]
     fn reactangles_collision() {
-        let r1 =  PhysObj {
+        let r1: PhysObj =  PhysObj {
                 id: Option::None,
                 velosity: Vector(1.,1.),
                 shape: Shape::Rectangle(Rectangle {
@@ -222,8 +291,188 @@ mod tests {
         let x = PhysObj::predict_collision(&r1, &r2);
         let y = PhysObj::predict_collision(&r1, &r3);
 
-        assert_eq!(x, Option::Some(Collision{location:Vector(5., 5.), time_offset: 4.5}));
-        assert_eq!(y, Option::Some(Collision{location:Vector(5., 6.), time_offset: 5.5}));
+        assert_approx_eq!(x, Option::Some(Collision{location:Vector(5., 5.), time_offset: 4.5}), Tolerance::Absolute(1e-5));
+        assert_approx_eq!(y, Option::Some(Collision{location:Vector(5., 6.), time_offset: 5.5}), Tolerance::Absolute(1e-5));
+    }
+
+    #[test]
+    fn reactangles_collision_far_from_origin() {
+        let offset = 1e8;
+        let r1: PhysObj<f64> = PhysObj {
+                id: Option::None,
+                velosity: Vector(1.,1.),
+                shape: Shape::Rectangle(Rectangle {
+                    location: Vector(offset, offset),
+                    dementions: Vector(1.,1.),
+                })
+        };
+
+        let r2 = PhysObj {
+                id: Option::None,
+                velosity: Vector(-1.,-1.),
+                shape: Shape::Rectangle(Rectangle {
+                    location: Vector(offset + 10., offset + 10.),
+                    dementions: Vector(1.,1.),
+                })
+        };
+
+        let x = PhysObj::predict_collision(&r1, &r2);
+
+        assert_eq!(x, Option::Some(Collision{location:Vector(offset + 5., offset + 5.), time_offset: 4.5}));
+    }
+
+    fn fixed_scene() -> (PhysObj<Fix16>, PhysObj<Fix16>, PhysObj<Fix16>, PhysObj<Fix16>) {
+        let f = Fix16::from_f64;
+        let c1 = PhysObj {
+            id: Option::None,
+            velosity: Vector(f(1.3), f(0.7)),
+            shape: Shape::Circle(Circle {
+                location: Vector(f(0.), f(0.)),
+                radius: f(1.),
+            }),
+        };
+        let c2 = PhysObj {
+            id: Option::None,
+            velosity: Vector(f(-0.9), f(-1.1)),
+            shape: Shape::Circle(Circle {
+                location: Vector(f(10.), f(9.)),
+                radius: f(1.5),
+            }),
+        };
+        let r1 = PhysObj {
+            id: Option::None,
+            velosity: Vector(f(1.), f(1.)),
+            shape: Shape::Rectangle(Rectangle {
+                location: Vector(f(0.), f(0.)),
+                dementions: Vector(f(1.), f(1.)),
+            }),
+        };
+        let r2 = PhysObj {
+            id: Option::None,
+            velosity: Vector(f(-1.), f(-1.)),
+            shape: Shape::Rectangle(Rectangle {
+                location: Vector(f(10.), f(12.)),
+                dementions: Vector(f(1.), f(1.)),
+            }),
+        };
+        return (c1, c2, r1, r2);
+    }
+
+    #[test]
+    fn fixed_point_collision() {
+        let (c1, c2, r1, r2) = fixed_scene();
+
+        let x = PhysObj::predict_collision(&r1, &r2);
+        assert_eq!(x, Option::Some(Collision{
+            location: Vector(Fix16::from_int(5), Fix16::from_int(6)),
+            time_offset: Fix16::from_f64(5.5),
+        }));
+
+        // raw Q16.16 values are the same on every platform
+        let y = PhysObj::predict_collision(&c1, &c2);
+        assert_eq!(y, Option::Some(Collision{
+            location: Vector(Fix16::from_raw(378496), Fix16::from_raw(244095)),
+            time_offset: Fix16::from_raw(254076),
+        }));
+    }
+
+    #[test]
+    fn fixed_point_repeated_runs() {
+        let run = || {
+            let (mut c1, mut c2, r1, r2) = fixed_scene();
+            let mut result = Vec::new();
+            for _ in 0..100 {
+                result.push(PhysObj::predict_collision(&c1, &c2));
+                result.push(PhysObj::predict_collision(&r1, &r2));
+                c1.shift(Fix16::from_f64(0.01));
+                c2.shift(Fix16::from_f64(0.01));
+            }
+            return result;
+        };
+
+        let first = run();
+        for _ in 0..5 {
+            assert_eq!(run(), first);
+        }
+    }
+
+    // shape type defined outside of the shape crate
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Diamond {
+        center: Vector,
+        radius: f32,
+    }
+
+    impl Bounded<f32> for Diamond {
+        fn to_frame(&self) -> Frame {
+            let r = Vector(self.radius, self.radius);
+            return Frame { from: self.center - r, to: self.center + r };
+        }
+    }
+
+    impl Translate<f32> for Diamond {
+        fn shift(&mut self, offset: &Vector) {
+            self.center += *offset;
+        }
+    }
+
+    #[test]
+    fn custom_shape() {
+        let mut d = PhysObj {
+            id: Option::None,
+            velosity: Vector(1., 1.),
+            shape: Diamond { center: Vector(0., 0.), radius: 0.5 },
+        };
+        let r: PhysObj = PhysObj {
+            id: Option::None,
+            velosity: Vector(-1., -1.),
+            shape: Shape::Rectangle(Rectangle {
+                location: Vector(10., 10.),
+                dementions: Vector(1., 1.),
+            }),
+        };
+
+        // the same as two rectangles of the same size
+        let x = PhysObj::predict_frame_collision(&d, &r);
+        assert_approx_eq!(x, Option::Some(Collision{location:Vector(5., 5.), time_offset: 4.5}), Tolerance::Absolute(1e-5));
+        assert_eq!(PhysObj::predict_frame_collision(&r, &d), x);
+
+        d.shift(2.);
+        assert_eq!(d.shape.center, Vector(2., 2.));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let o: PhysObj = PhysObj {
+            id: Option::Some(7),
+            velosity: Vector(1., -1.),
+            shape: Shape::Circle(Circle {
+                location: Vector(0., 0.),
+                radius: 1.,
+            }),
+        };
+
+        let json = serde_json::to_string(&o).unwrap();
+        assert_eq!(json, r#"{"id":7,"shape":{"circle":{"location":[0.0,0.0],"radius":1.0}},"velosity":[1.0,-1.0]}"#);
+        assert_eq!(serde_json::from_str::<PhysObj>(&json).unwrap(), o);
+
+        let ron = ron::to_string(&o).unwrap();
+        assert_eq!(ron::from_str::<PhysObj>(&ron).unwrap(), o);
+
+        let c: Collision = Collision { location: Vector(5., 6.), time_offset: 5.5 };
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(serde_json::from_str::<Collision>(&json).unwrap(), c);
+        let ron = ron::to_string(&c).unwrap();
+        assert_eq!(ron::from_str::<Collision>(&ron).unwrap(), c);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_fixed_point_snapshot() {
+        let (c1, ..) = fixed_scene();
+
+        let ron = ron::to_string(&c1).unwrap();
+        assert_eq!(ron::from_str::<PhysObj<Fix16>>(&ron).unwrap(), c1);
     }
-    
 }
@@ -1 +1,3 @@
-pub mod range;
\ No newline at end of file
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+pub mod range;
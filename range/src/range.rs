@@ -1,8 +1,12 @@
-#[derive(Debug, Clone, PartialEq)]
-pub struct Range(pub f32, pub f32);
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
 
-impl Range {
-    pub fn new(start: f32, end: f32) -> Self {
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Range<T = f32>(pub T, pub T);
+
+impl<T: Scalar> Range<T> {
+    pub fn new(start: T, end: T) -> Self {
         if start < end {
             Range(start, end)
         } else {
@@ -10,7 +14,7 @@ impl Range {
         }
     }
 
-    pub fn intercept(&self, r: &Range) -> Option<Range> {
+    pub fn intercept(&self, r: &Range<T>) -> Option<Range<T>> {
         let start = self.0.max(r.0);
         let end = self.1.min(r.1);
 
@@ -21,7 +25,7 @@ impl Range {
         }
     }
 
-    pub fn clamp_start(&self, x: f32) -> Option<Range> {
+    pub fn clamp_start(&self, x: T) -> Option<Range<T>> {
         let c = self.0.max(x);
         if c <= self.1 {
             Option::Some(Range(c, self.1))
@@ -30,7 +34,7 @@ impl Range {
         }
     }
 
-    pub fn clamp_end(&self, y: f32) -> Option<Range> {
+    pub fn clamp_end(&self, y: T) -> Option<Range<T>> {
         let c = self.1.min(y);
         if c >= self.0 {
             Option::Some(Range(self.0, c))
@@ -39,14 +43,26 @@ impl Range {
         }
     }
 
-    pub fn center(&self) -> f32 {
-        return (self.0 + self.1) / 2.;
+    pub fn center(&self) -> T {
+        return (self.0 + self.1) / T::from_f32(2.);
+    }
+}
+
+impl<T> ApproxEq for Range<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Range<T>, tolerance: Tolerance<T>) -> bool {
+        return self.0.approx_eq(&other.0, tolerance) && self.1.approx_eq(&other.1, tolerance);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vector::{assert_approx_eq, assert_approx_ne};
 
     #[test]
     fn intercept() {
@@ -75,4 +91,34 @@ mod tests {
 
         assert_eq!(r1.clamp_end(20.), Some(Range(1., 3.)));
     }
+
+    #[test]
+    fn double_precision() {
+        let r1: Range<f64> = Range::new(3., 1.);
+        assert_eq!(r1, Range(1., 3.));
+        assert_eq!(r1.center(), 2.);
+        assert_eq!(r1.intercept(&Range(2.5, 10.)), Some(Range(2.5, 3.)));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let r = Range(0.1f64 + 0.2, 1.);
+
+        assert_ne!(r, Range(0.3, 1.));
+        assert_approx_eq!(r, Range(0.3, 1.));
+        assert_approx_ne!(r, Range(0.3, 1.01));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let r = Range(1., 3.5);
+
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, "[1.0,3.5]");
+        assert_eq!(serde_json::from_str::<Range>(&json).unwrap(), r);
+
+        let ron = ron::to_string(&r).unwrap();
+        assert_eq!(ron::from_str::<Range>(&ron).unwrap(), r);
+    }
 }
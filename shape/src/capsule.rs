@@ -0,0 +1,246 @@
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
+use vector::vector::Vector;
+
+use crate::circle::Circle;
+use crate::frame::Frame;
+use crate::gjk::SupportMap;
+use crate::ray::{Ray, RayHit};
+use crate::segment::Segment;
+
+/**
+ * All points within radius of the segment: a rectangle with two half circles at the ends.
+ * A vertical capsule slides over the seams between tiles instead of catching on their corners.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule<T = f32> {
+    pub segment: Segment<T>,
+    pub radius: T,
+}
+
+impl<T: Scalar> Capsule<T> {
+    pub fn area(&self) -> T {
+        let two = T::from_f32(2.);
+        return two * self.radius * self.segment.vector.size() + T::PI * self.radius * self.radius;
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        let f = self.segment.to_frame();
+        let r = Vector(self.radius, self.radius);
+        return Frame {
+            from: f.from - r,
+            to: f.to + r,
+        };
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        self.segment.shift(offset);
+    }
+
+    /**
+     * Like for Circle, the radius is scaled by the area scale of the transform
+     */
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        self.segment.transform(t);
+        self.radius *= t.determinant().abs().sqrt();
+    }
+
+    /**
+     * Bounding frame of the transformed end caps
+     */
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        let start = Circle {
+            location: self.segment.location,
+            radius: self.radius,
+        }
+        .transformed_frame(t);
+        let end = Circle {
+            location: self.segment.end(),
+            radius: self.radius,
+        }
+        .transformed_frame(t);
+        return Frame {
+            from: Vector(start.from.0.min(end.from.0), start.from.1.min(end.from.1)),
+            to: Vector(start.to.0.max(end.to.0), start.to.1.max(end.to.1)),
+        };
+    }
+
+    /**
+     * True if the point is strictly inside the capsule
+     */
+    pub fn contains_point(&self, p: &Vector<T>) -> bool {
+        return self.segment.closest_point(p).distance_squared(p) < self.radius * self.radius;
+    }
+
+    /**
+     * Point of the capsule nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        let center = self.segment.closest_point(p);
+        let d = *p - center;
+        let distance = d.size();
+        if distance <= self.radius {
+            return *p;
+        }
+        return center + d * (self.radius / distance);
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.signed_distance(p).max(T::ZERO);
+    }
+
+    /**
+     * Distance to the surface, negative inside
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return self.segment.distance_to_point(p) - self.radius;
+    }
+
+    /**
+     * Nearest hit of the two end caps and the two straight sides
+     */
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        if self.contains_point(&ray.origin) {
+            return ray.inside();
+        }
+
+        let mut best: Option<RayHit<T>> = None;
+        let mut consider = |hit: Option<RayHit<T>>| {
+            if let Some(h) = hit {
+                if best.is_none_or(|b| h.distance < b.distance) {
+                    best = Some(h);
+                }
+            }
+        };
+        for location in self.ends() {
+            consider(Circle { location, radius: self.radius }.raycast(ray));
+        }
+
+        let mut side = self.segment.vector.perp_left();
+        side.normalize();
+        if side.size() > T::ZERO {
+            for offset in [side * self.radius, -side * self.radius] {
+                let mut s = self.segment;
+                consider(s.shift(&offset).raycast(ray));
+            }
+        }
+        return best;
+    }
+
+    /**
+     * The two end points of the inner segment
+     */
+    pub fn ends(&self) -> [Vector<T>; 2] {
+        return [self.segment.location, self.segment.end()];
+    }
+}
+
+impl<T: Scalar> SupportMap<T> for Capsule<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        let mut d = *direction;
+        d.normalize();
+        return self.segment.support(direction) + d * self.radius;
+    }
+}
+
+impl<T> ApproxEq for Capsule<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Capsule<T>, tolerance: Tolerance<T>) -> bool {
+        return self.segment.approx_eq(&other.segment, tolerance)
+            && self.radius.approx_eq(&other.radius, tolerance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn standing() -> Capsule {
+        return Capsule {
+            segment: Segment {
+                location: Vector(0., 0.),
+                vector: Vector(0., 2.),
+            },
+            radius: 1.,
+        };
+    }
+
+    #[test]
+    fn area() {
+        assert_eq!(standing().area(), 4. + PI);
+    }
+
+    #[test]
+    fn to_frame() {
+        assert_eq!(standing().to_frame(), Frame { from: Vector(-1., -1.), to: Vector(1., 3.) });
+    }
+
+    #[test]
+    fn transform() {
+        let mut c = standing();
+        let f = c.transformed_frame(&Transform2D::from_rotation(PI / 2.));
+        assert!((f.from - Vector(-3., -1.)).size() < 1e-5);
+        assert!((f.to - Vector(1., 1.)).size() < 1e-5);
+
+        c.transform(&Transform2D::from_scale(Vector(2., 2.)));
+        assert_eq!(c.segment.vector, Vector(0., 4.));
+        assert_eq!(c.radius, 2.);
+
+        c.shift(&Vector(1., 0.));
+        assert_eq!(c.ends(), [Vector(1., 0.), Vector(1., 4.)]);
+    }
+
+    #[test]
+    fn contains_point() {
+        let c = standing();
+
+        assert!(c.contains_point(&Vector(0.5, 1.)));
+        assert!(c.contains_point(&Vector(0., 2.9)));
+        assert!(!c.contains_point(&Vector(0.8, 2.8)));
+        assert!(!c.contains_point(&Vector(1., 1.)));
+    }
+
+    #[test]
+    fn closest_point() {
+        let c = standing();
+
+        assert_eq!(c.closest_point(&Vector(3., 1.)), Vector(1., 1.));
+        assert_eq!(c.closest_point(&Vector(0., -4.)), Vector(0., -1.));
+        assert_eq!(c.closest_point(&Vector(0.5, 0.5)), Vector(0.5, 0.5));
+    }
+
+    #[test]
+    fn raycast() {
+        let c = standing();
+
+        let side = c.raycast(&Ray::new(Vector(-5., 1.), Vector(1., 0.))).unwrap();
+        assert_eq!(side, RayHit { distance: 4., point: Vector(-1., 1.), normal: Vector(-1., 0.) });
+
+        let bottom = c.raycast(&Ray::new(Vector(0., -5.), Vector(0., 1.))).unwrap();
+        assert_eq!(bottom, RayHit { distance: 4., point: Vector(0., -1.), normal: Vector(0., -1.) });
+
+        let corner = c.raycast(&Ray::new(Vector(-3., -3.), Vector(1., 1.))).unwrap();
+        assert!((corner.distance - (18f32.sqrt() - 1.)).abs() < 1e-5);
+        assert!((corner.normal - Vector(-0.5f32.sqrt(), -0.5f32.sqrt())).size() < 1e-5);
+
+        assert_eq!(c.raycast(&Ray::new(Vector(-5., 4.), Vector(1., 0.))), None);
+        assert_eq!(c.raycast(&Ray::new(Vector(0., 1.), Vector(1., 0.))).unwrap().distance, 0.);
+    }
+
+    #[test]
+    fn distance() {
+        let c = standing();
+
+        assert_eq!(c.distance_to_point(&Vector(3., 1.)), 2.);
+        assert_eq!(c.distance_to_point(&Vector(0., 1.)), 0.);
+        assert_eq!(c.signed_distance(&Vector(0.5, 1.)), -0.5);
+        assert_eq!(c.signed_distance(&Vector(0., 5.)), 2.);
+    }
+}
@@ -0,0 +1,489 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::vector::Vector;
+
+use crate::contact::Contact;
+use crate::frame::Frame;
+use crate::sat;
+use crate::segment::Segment;
+use crate::shape::Shape;
+use crate::traits::{Bounded, Translate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainError {
+    TooFewVertices,
+    // two consecutive vertices coincide
+    ZeroLengthEdge,
+    // the one way flags do not match the edges, only from deserialization
+    OneWayCount,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            ChainError::TooFewVertices => write!(f, "chain needs at least 2 vertices, a loop 3"),
+            ChainError::ZeroLengthEdge => write!(f, "chain has an edge of zero length"),
+            ChainError::OneWayCount => write!(f, "chain needs one one way flag per edge"),
+        };
+    }
+}
+
+/**
+ * Polyline of edges for terrain, optionally closed into a loop. The front of an edge
+ * is on the left of its direction, so ground drawn left to right faces up.
+ *
+ * Shapes sliding along the chain do not catch on the vertices between edges: each edge
+ * looks at its neighbours (ghost vertices) and only reports normals that really belong
+ * to it. Ghost vertices can also be given past the ends of an open chain, so separate
+ * chains join seamlessly.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// saved chains are rebuilt edge by edge, the one way flags must still match the edges
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "ChainData<T>",
+        into = "ChainData<T>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct Chain<T = f32> {
+    vertices: Vec<Vector<T>>,
+    // per edge, one way edges only collide with shapes in front of them
+    one_way: Vec<bool>,
+    looped: bool,
+    ghost_start: Option<Vector<T>>,
+    ghost_end: Option<Vector<T>>,
+}
+
+impl<T: Scalar> Chain<T> {
+    pub fn new(vertices: &[Vector<T>], looped: bool) -> Result<Chain<T>, ChainError> {
+        if vertices.len() < 2 || (looped && vertices.len() < 3) {
+            return Err(ChainError::TooFewVertices);
+        }
+        let mut chain = Chain {
+            vertices: vertices.to_vec(),
+            one_way: Vec::new(),
+            looped,
+            ghost_start: None,
+            ghost_end: None,
+        };
+        chain.one_way = vec![false; chain.edge_count()];
+        if chain.edges().any(|e| e.vector == Vector(T::ZERO, T::ZERO)) {
+            return Err(ChainError::ZeroLengthEdge);
+        }
+        return Ok(chain);
+    }
+
+    /**
+     * Vertices before the first and after the last one, ignored by loops
+     */
+    pub fn with_ghost_vertices(self, start: Option<Vector<T>>, end: Option<Vector<T>>) -> Chain<T> {
+        if self.looped {
+            return self;
+        }
+        return Chain {
+            ghost_start: start,
+            ghost_end: end,
+            ..self
+        };
+    }
+
+    pub fn vertices(&self) -> &[Vector<T>] {
+        return &self.vertices;
+    }
+
+    pub fn is_looped(&self) -> bool {
+        return self.looped;
+    }
+
+    pub fn edge_count(&self) -> usize {
+        return if self.looped {
+            self.vertices.len()
+        } else {
+            self.vertices.len() - 1
+        };
+    }
+
+    /**
+     * Edge from the vertex i to the next one
+     */
+    pub fn edge(&self, i: usize) -> Segment<T> {
+        let a = self.vertices[i];
+        let b = self.vertices[(i + 1) % self.vertices.len()];
+        return Segment {
+            location: a,
+            vector: b - a,
+        };
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        return (0..self.edge_count()).map(|i| self.edge(i));
+    }
+
+    /**
+     * Edges past the end of the chain are two way
+     */
+    pub fn is_one_way(&self, edge: usize) -> bool {
+        return self.one_way.get(edge).copied().unwrap_or(false);
+    }
+
+    /**
+     * Edges past the end of the chain are ignored
+     */
+    pub fn set_one_way(&mut self, edge: usize, one_way: bool) {
+        if let Some(flag) = self.one_way.get_mut(edge) {
+            *flag = one_way;
+        }
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        return Frame::from_points(&self.vertices);
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        for v in self.vertices.iter_mut() {
+            *v += *offset;
+        }
+        self.ghost_start = self.ghost_start.map(|g| g + *offset);
+        self.ghost_end = self.ghost_end.map(|g| g + *offset);
+    }
+
+    /**
+     * Contacts of the shape with the edges, the normals point from the chain to the shape.
+     * Every edge pushes the shape back to the side its center is on,
+     * one way edges ignore shapes whose center is behind them.
+     */
+    pub fn contacts(&self, shape: &Shape<T>) -> Vec<Contact<T>> {
+        let frame = shape.to_frame();
+        let center = frame.center();
+        let hull = shape.hull();
+        let mut contacts = Vec::new();
+
+        for i in 0..self.edge_count() {
+            let edge = self.edge(i);
+            if !edge.to_frame().is_intercected(&frame) {
+                continue;
+            }
+            let points = [edge.location, edge.end()];
+            let Some(p) = sat::penetration(&points, T::ZERO, hull.points(), hull.radius) else {
+                continue;
+            };
+
+            let mut face = edge.vector.perp_left();
+            face.normalize();
+            if face.dot(&(center - edge.location)) < T::ZERO {
+                if self.one_way[i] {
+                    continue;
+                }
+                face = -face;
+            }
+
+            let p = match self.region(i, &p.normal, &face) {
+                Region::Owned => p,
+                Region::Neighbour => continue,
+                Region::Face => {
+                    // the shape overlaps the edge, but is pushed along its face normal only
+                    let deepest = sat::support(hull.points(), &-face).dot(&face) - hull.radius;
+                    let depth = edge.location.dot(&face) - deepest;
+                    if depth <= T::ZERO {
+                        continue;
+                    }
+                    sat::Penetration { normal: face, depth }
+                }
+            };
+            let (points, point_count) = p.manifold(&points, T::ZERO, hull.points(), hull.radius);
+            contacts.push(Contact {
+                normal: p.normal,
+                depth: p.depth,
+                points,
+                point_count,
+            });
+        }
+        return contacts;
+    }
+
+    /**
+     * Which edge a contact normal of the edge i belongs to. Normals tilted past the end of
+     * an edge are only valid around a convex corner; the corner with a previous edge of the
+     * chain is reported by that edge, so it is not reported twice.
+     */
+    fn region(&self, i: usize, normal: &Vector<T>, face: &Vector<T>) -> Region {
+        let edge = self.edge(i).vector;
+        let along = normal.dot(&edge);
+        if normal.dot(face) < T::ZERO {
+            return Region::Face;
+        }
+        // convex as seen from the side of the face
+        let convex = |from: &Vector<T>, to: &Vector<T>| from.perp_dot(to) * face.dot(&edge.perp_left()) <= T::ZERO;
+
+        if along > T::ZERO {
+            return match self.next_vertex(i) {
+                None => Region::Owned,
+                Some(next) => {
+                    let next_edge = next - (edge + self.vertices[i]);
+                    if convex(&edge, &next_edge) && normal.dot(&next_edge) <= T::ZERO {
+                        Region::Owned
+                    } else {
+                        Region::Face
+                    }
+                }
+            };
+        }
+        if along < T::ZERO {
+            let ghost = i == 0 && !self.looped;
+            return match self.previous_vertex(i) {
+                None => Region::Owned,
+                Some(previous) => {
+                    let previous_edge = self.vertices[i] - previous;
+                    if !convex(&previous_edge, &edge) || normal.dot(&previous_edge) < T::ZERO {
+                        Region::Face
+                    } else if ghost {
+                        Region::Owned
+                    } else {
+                        Region::Neighbour
+                    }
+                }
+            };
+        }
+        return Region::Owned;
+    }
+
+    fn previous_vertex(&self, i: usize) -> Option<Vector<T>> {
+        if i > 0 {
+            return Some(self.vertices[i - 1]);
+        }
+        if self.looped {
+            return self.vertices.last().copied();
+        }
+        return self.ghost_start;
+    }
+
+    fn next_vertex(&self, i: usize) -> Option<Vector<T>> {
+        let n = self.vertices.len();
+        if i + 2 < n || self.looped {
+            return Some(self.vertices[(i + 2) % n]);
+        }
+        return self.ghost_end;
+    }
+}
+
+/**
+ * Where a contact normal of an edge points
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    // the face or a corner of the edge
+    Owned,
+    // a corner reported by the neighbouring edge
+    Neighbour,
+    // nobody's, like the inside of a concave corner or the back of the edge
+    Face,
+}
+
+/**
+ * Chain as it is saved: the vertex list, one flag per edge and the ghost vertices
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ChainData<T> {
+    vertices: Vec<Vector<T>>,
+    one_way: Vec<bool>,
+    looped: bool,
+    ghost_start: Option<Vector<T>>,
+    ghost_end: Option<Vector<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<ChainData<T>> for Chain<T> {
+    type Error = ChainError;
+
+    fn try_from(data: ChainData<T>) -> Result<Chain<T>, ChainError> {
+        let mut chain = Chain::new(&data.vertices, data.looped)?.with_ghost_vertices(data.ghost_start, data.ghost_end);
+        if data.one_way.len() != chain.edge_count() {
+            return Err(ChainError::OneWayCount);
+        }
+        chain.one_way = data.one_way;
+        return Ok(chain);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<Chain<T>> for ChainData<T> {
+    fn from(chain: Chain<T>) -> ChainData<T> {
+        return ChainData {
+            vertices: chain.vertices,
+            one_way: chain.one_way,
+            looped: chain.looped,
+            ghost_start: chain.ghost_start,
+            ghost_end: chain.ghost_end,
+        };
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Chain<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Chain::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Chain<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Chain::shift(self, offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::Circle;
+    use crate::rectangle::Rectangle;
+    use vector::assert_approx_eq;
+
+    fn ball(x: f32, y: f32) -> Shape {
+        return Shape::Circle(Circle {
+            location: Vector(x, y),
+            radius: 1.,
+        });
+    }
+
+    // from the bottom left corner
+    fn crate_box(x: f32, y: f32) -> Shape {
+        return Shape::Rectangle(Rectangle {
+            location: Vector(x + 0.4, y + 0.4),
+            dementions: Vector(0.8, 0.8),
+        });
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(Chain::new(&[Vector(0., 0.)], false), Err(ChainError::TooFewVertices));
+        assert_eq!(Chain::new(&[Vector(0., 0.), Vector(1., 0.)], true), Err(ChainError::TooFewVertices));
+        assert_eq!(
+            Chain::new(&[Vector(0., 0.), Vector(1., 0.), Vector(1., 0.)], false),
+            Err(ChainError::ZeroLengthEdge)
+        );
+        assert_eq!(
+            Chain::new(&[Vector(0., 0.), Vector(1., 0.), Vector(0., 0.)], true),
+            Err(ChainError::ZeroLengthEdge)
+        );
+    }
+
+    #[test]
+    fn edges_and_frame() {
+        let mut chain = Chain::new(&[Vector(0., 0.), Vector(2., 0.), Vector(2., 1.)], true).unwrap();
+        chain.shift(&Vector(1., 1.));
+
+        assert_eq!(chain.edge_count(), 3);
+        assert_eq!(chain.edge(2), Segment { location: Vector(3., 2.), vector: Vector(-2., -1.) });
+        assert_eq!(chain.to_frame(), Frame { from: Vector(1., 1.), to: Vector(3., 2.) });
+    }
+
+    #[test]
+    fn sliding_over_vertices() {
+        let ground = Chain::new(&[Vector(0., 0.), Vector(1., 0.), Vector(2., 0.), Vector(3., 0.)], false).unwrap();
+
+        // a corner of the box is just past the vertex, a lone segment would push it sideways
+        for x in [0.98, 1.4, 1.99] {
+            let contacts = ground.contacts(&crate_box(x, -0.05));
+            assert!(!contacts.is_empty());
+            for c in contacts {
+                assert_eq!(c.normal, Vector(0., 1.));
+                assert_approx_eq!(c.depth, 0.05);
+            }
+        }
+        assert!(ground.contacts(&crate_box(1., 0.1)).is_empty());
+    }
+
+    #[test]
+    fn rolling_over_a_hill() {
+        let hill = Chain::new(&[Vector(-2., -2.), Vector(0., 0.), Vector(2., -2.)], false).unwrap();
+
+        // the top vertex is reported once
+        let contacts = hill.contacts(&ball(0., 0.9));
+        assert_eq!(contacts.len(), 1);
+        assert_approx_eq!(contacts[0].normal, Vector(0., 1.));
+        assert_approx_eq!(contacts[0].depth, 0.1);
+
+        let contacts = hill.contacts(&ball(1., 0.));
+        assert_eq!(contacts.len(), 1);
+        assert_approx_eq!(contacts[0].normal, Vector(0.70710677, 0.70710677));
+    }
+
+    #[test]
+    fn resting_in_a_valley() {
+        let valley = Chain::new(&[Vector(-2., 2.), Vector(0., 0.), Vector(2., 2.)], false).unwrap();
+
+        let contacts = valley.contacts(&ball(0., 1.3));
+        assert_eq!(contacts.len(), 2);
+        assert_approx_eq!(contacts[0].normal, Vector(0.70710677, 0.70710677));
+        assert_approx_eq!(contacts[1].normal, Vector(-0.70710677, 0.70710677));
+    }
+
+    #[test]
+    fn ghost_vertices() {
+        // the end of this chain meets more flat ground
+        let open = Chain::new(&[Vector(0., 0.), Vector(1., 0.)], false).unwrap();
+        let joined = open.clone().with_ghost_vertices(None, Some(Vector(2., 0.)));
+
+        let beyond = crate_box(0.98, -0.05);
+        assert_eq!(open.contacts(&beyond)[0].normal, Vector(1., 0.));
+        assert_eq!(joined.contacts(&beyond)[0].normal, Vector(0., 1.));
+    }
+
+    #[test]
+    fn one_way_edges() {
+        let mut platform = Chain::new(&[Vector(0., 0.), Vector(4., 0.)], false).unwrap();
+
+        let contacts = platform.contacts(&ball(2., -0.8));
+        assert_eq!(contacts[0].normal, Vector(0., -1.));
+
+        platform.set_one_way(0, true);
+        assert!(platform.is_one_way(0));
+        // like tiles outside of a tile map
+        platform.set_one_way(1, true);
+        assert!(!platform.is_one_way(1));
+        assert!(platform.contacts(&ball(2., -0.8)).is_empty());
+        let contacts = platform.contacts(&ball(2., 0.8));
+        assert_eq!(contacts[0].normal, Vector(0., 1.));
+        assert_approx_eq!(contacts[0].depth, 0.2);
+    }
+
+    #[test]
+    fn closed_loop() {
+        // clockwise, so the front of every edge faces out
+        let island = Chain::new(&[Vector(0., 0.), Vector(0., 2.), Vector(4., 2.), Vector(4., 0.)], true).unwrap();
+
+        let contacts = island.contacts(&crate_box(1., 1.9));
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].normal, Vector(0., 1.));
+        assert_eq!(contacts[0].point_count, 2);
+
+        let contacts = island.contacts(&ball(4.5, 2.5));
+        assert_eq!(contacts.len(), 1);
+        assert_approx_eq!(contacts[0].normal, Vector(0.70710677, 0.70710677));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_validates() {
+        let mut chain = Chain::new(&[Vector(0., 0.), Vector(1., 0.), Vector(2., 1.)], false)
+            .unwrap()
+            .with_ghost_vertices(Some(Vector(-1., 0.)), None);
+        chain.set_one_way(1, true);
+        let json = serde_json::to_string(&chain).unwrap();
+        assert_eq!(serde_json::from_str::<Chain>(&json).unwrap(), chain);
+
+        let load = |json: &str| serde_json::from_str::<Chain>(json).map_err(|e| e.to_string());
+        let error = load(r#"{"vertices":[[0,0],[1,0]],"one_way":[],"looped":false,"ghost_start":null,"ghost_end":null}"#);
+        assert!(error.unwrap_err().contains("one one way flag per edge"));
+        let error = load(r#"{"vertices":[[0,0],[0,0]],"one_way":[false],"looped":false,"ghost_start":null,"ghost_end":null}"#);
+        assert!(error.unwrap_err().contains("zero length"));
+        let error = load(r#"{"vertices":[[0,0],[1,0]],"one_way":[false,false],"looped":true,"ghost_start":null,"ghost_end":null}"#);
+        assert!(error.unwrap_err().contains("at least 2 vertices"));
+    }
+}
@@ -1,33 +1,128 @@
 use super::frame::Frame;
-use std::f32::consts::PI;
+use super::gjk::SupportMap;
+use super::ray::{Ray, RayHit};
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
 use vector::vector::Vector;
 
-#[derive(Debug)]
-pub struct Circle {
-    pub location: Vector,
-    pub radius: f32,
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle<T = f32> {
+    pub location: Vector<T>,
+    pub radius: T,
 }
 
-impl Circle {
-    pub fn area(&self) -> f32 {
-        return self.radius * self.radius * PI;
+impl<T: Scalar> Circle<T> {
+    pub fn area(&self) -> T {
+        return self.radius * self.radius * T::PI;
     }
 
-    pub fn to_frame(&self) -> Frame {
+    pub fn to_frame(&self) -> Frame<T> {
         return Frame {
             from: Vector(self.location.0 - self.radius, self.location.1 - self.radius),
             to: Vector(self.location.0 + self.radius, self.location.1 + self.radius),
         };
     }
+
+    /**
+     * Tight bounding frame of the circle after the transform.
+     * Non-uniform scale turns the circle into an ellipse, the frame bounds the ellipse.
+     */
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        let center = t.apply_point(&self.location);
+        let extent = Vector(
+            Vector(t.x_axis.0, t.y_axis.0).size() * self.radius,
+            Vector(t.x_axis.1, t.y_axis.1).size() * self.radius,
+        );
+        return Frame {
+            from: center - extent,
+            to: center + extent,
+        };
+    }
+
+    /**
+     * Moves the center and scales the radius by the area scale of the transform
+     * (exact for uniform scale, the circle can not become an ellipse)
+     */
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        self.location = t.apply_point(&self.location);
+        self.radius *= t.determinant().abs().sqrt();
+    }
+
+    /**
+     * Point of the circle nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        let d = *p - self.location;
+        let distance = d.size();
+        if distance <= self.radius {
+            return *p;
+        }
+        return self.location + d * (self.radius / distance);
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.signed_distance(p).max(T::ZERO);
+    }
+
+    /**
+     * Distance to the circle line, negative inside
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return self.location.distance(p) - self.radius;
+    }
+
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        let m = ray.origin - self.location;
+        let c = m.size_squared() - self.radius * self.radius;
+        if c < T::ZERO {
+            return ray.inside();
+        }
+
+        let a = ray.direction.size_squared();
+        let b = m.dot(&ray.direction);
+        // outside and moving away
+        if a == T::ZERO || b > T::ZERO {
+            return None;
+        }
+        let discriminant = b * b - a * c;
+        if discriminant < T::ZERO {
+            return None;
+        }
+        let t = (-b - discriminant.sqrt()) / a;
+        return ray.hit(t, ray.origin + ray.direction * t - self.location);
+    }
+}
+
+impl<T: Scalar> SupportMap<T> for Circle<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        let mut d = *direction;
+        d.normalize();
+        return self.location + d * self.radius;
+    }
+}
+
+impl<T> ApproxEq for Circle<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Circle<T>, tolerance: Tolerance<T>) -> bool {
+        return self.location.approx_eq(&other.location, tolerance)
+            && self.radius.approx_eq(&other.radius, tolerance);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f32::consts::PI;
 
     #[test]
     fn area() {
-        let v = Circle {
+        let v: Circle = Circle {
             location: Vector(0., 0.),
             radius: 1.,
         };
@@ -48,4 +143,53 @@ mod tests {
         assert_eq!(a.to.1, 1.);
     }
 
+    #[test]
+    fn transform() {
+        let mut c = Circle {
+            location: Vector(1., 0.),
+            radius: 1.,
+        };
+        let t = Transform2D::from_trs(Vector(0., 0.), std::f32::consts::FRAC_PI_2, Vector(2., 1.));
+
+        let f = c.transformed_frame(&t);
+        assert!((f.from - Vector(-1., 0.)).size() < 1e-5);
+        assert!((f.to - Vector(1., 4.)).size() < 1e-5);
+
+        c.transform(&Transform2D::from_scale(Vector(3., 3.)));
+        assert_eq!(c.location, Vector(3., 0.));
+        assert_eq!(c.radius, 3.);
+    }
+
+    #[test]
+    fn raycast() {
+        let c = Circle {
+            location: Vector(3., 0.),
+            radius: 1.,
+        };
+
+        let hit = c.raycast(&Ray::new(Vector(0., 0.), Vector(2., 0.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 2., point: Vector(2., 0.), normal: Vector(-1., 0.) });
+
+        // grazing the top
+        let hit = c.raycast(&Ray::new(Vector(0., 1.), Vector(1., 0.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 3., point: Vector(3., 1.), normal: Vector(0., 1.) });
+
+        assert_eq!(c.raycast(&Ray::new(Vector(0., 1.5), Vector(1., 0.))), None);
+        assert_eq!(c.raycast(&Ray::new(Vector(0., 0.), Vector(-1., 0.))), None);
+        assert_eq!(c.raycast(&Ray::new(Vector(3.5, 0.), Vector(1., 0.))).unwrap().distance, 0.);
+    }
+
+    #[test]
+    fn distance() {
+        let c = Circle {
+            location: Vector(1., 1.),
+            radius: 2.,
+        };
+
+        assert_eq!(c.closest_point(&Vector(1., 5.)), Vector(1., 3.));
+        assert_eq!(c.closest_point(&Vector(2., 1.)), Vector(2., 1.));
+        assert_eq!(c.distance_to_point(&Vector(1., 5.)), 2.);
+        assert_eq!(c.distance_to_point(&Vector(2., 1.)), 0.);
+        assert_eq!(c.signed_distance(&Vector(2., 1.)), -1.);
+    }
 }
@@ -0,0 +1,178 @@
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::vector::Vector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactError {
+    // a contact has at most two points
+    TooManyPoints,
+}
+
+impl fmt::Display for ContactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            ContactError::TooManyPoints => write!(f, "contact has at most 2 points"),
+        };
+    }
+}
+
+/**
+ * How two overlapping shapes touch: moving the second shape by normal * depth
+ * separates them. The points lie halfway between the two surfaces.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// stored with the used points only, see ContactData
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "ContactData<T>",
+        into = "ContactData<T>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct Contact<T = f32> {
+    // unit length, points from the first shape to the second
+    pub normal: Vector<T>,
+    // zero for shapes that only share a point, like crossing segments
+    pub depth: T,
+    // only the first point_count points are used
+    pub points: [Vector<T>; 2],
+    pub point_count: usize,
+}
+
+impl<T: Scalar> Contact<T> {
+    pub fn points(&self) -> &[Vector<T>] {
+        return &self.points[..self.point_count];
+    }
+
+    /**
+     * The same contact seen from the second shape
+     */
+    pub fn flip(&self) -> Contact<T> {
+        return Contact {
+            normal: -self.normal,
+            ..*self
+        };
+    }
+
+    /**
+     * Offset that moves the second shape out of the first one
+     */
+    pub fn separation(&self) -> Vector<T> {
+        return self.normal * self.depth;
+    }
+}
+
+/**
+ * Serialized form of a contact, with the used points as a list
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ContactData<T> {
+    normal: Vector<T>,
+    depth: T,
+    points: Vec<Vector<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<ContactData<T>> for Contact<T> {
+    type Error = ContactError;
+
+    fn try_from(data: ContactData<T>) -> Result<Contact<T>, ContactError> {
+        if data.points.len() > 2 {
+            return Err(ContactError::TooManyPoints);
+        }
+        let mut points = [Vector::default(); 2];
+        points[..data.points.len()].copy_from_slice(&data.points);
+        return Ok(Contact {
+            normal: data.normal,
+            depth: data.depth,
+            points,
+            point_count: data.points.len(),
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<Contact<T>> for ContactData<T> {
+    fn from(contact: Contact<T>) -> ContactData<T> {
+        return ContactData {
+            normal: contact.normal,
+            depth: contact.depth,
+            points: contact.points().to_vec(),
+        };
+    }
+}
+
+impl<T> ApproxEq for Contact<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Contact<T>, tolerance: Tolerance<T>) -> bool {
+        return self.point_count == other.point_count
+            && self.normal.approx_eq(&other.normal, tolerance)
+            && self.depth.approx_eq(&other.depth, tolerance)
+            && self
+                .points()
+                .iter()
+                .zip(other.points())
+                .all(|(a, b)| a.approx_eq(b, tolerance));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::{assert_approx_eq, assert_approx_ne};
+
+    fn resting() -> Contact {
+        return Contact {
+            normal: Vector(0., 1.),
+            depth: 0.5,
+            points: [Vector(-1., 0.), Vector(1., 0.)],
+            point_count: 2,
+        };
+    }
+
+    #[test]
+    fn flip_and_separation() {
+        let c = resting();
+
+        assert_eq!(c.separation(), Vector(0., 0.5));
+        assert_eq!(c.flip().separation(), Vector(0., -0.5));
+        assert_eq!(c.flip().points(), c.points());
+    }
+
+    #[test]
+    fn approx_eq_ignores_unused_points() {
+        let mut a = resting();
+        a.point_count = 1;
+        let mut b = a;
+        b.points[1] = Vector(5., 5.);
+
+        assert_approx_eq!(a, b);
+        assert_approx_ne!(a, resting());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keeps_used_points() {
+        let mut c = resting();
+        c.point_count = 1;
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(json, r#"{"normal":[0.0,1.0],"depth":0.5,"points":[[-1.0,0.0]]}"#);
+        let loaded = serde_json::from_str::<Contact>(&json).unwrap();
+        assert_eq!(loaded.points(), c.points());
+        assert_approx_eq!(loaded, c);
+
+        let error = serde_json::from_str::<Contact>(r#"{"normal":[0,1],"depth":0,"points":[[0,0],[1,0],[2,0]]}"#);
+        assert!(error.unwrap_err().to_string().contains("at most 2 points"));
+    }
+}
@@ -0,0 +1,877 @@
+/*!
+ * Smooth paths for moving platforms and camera rails: Bezier curves and
+ * Catmull-Rom splines through a list of points.
+ *
+ * Curves are parameterized from 0 to their domain (1 for a Bezier curve, the
+ * number of pieces for a spline), which does not move at constant speed.
+ * ArcLength maps travelled distances back to parameters.
+ */
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::vector::Vector;
+
+use crate::frame::Frame;
+use crate::segment::Segment;
+
+// Gauss-Legendre quadrature with 5 points on [-1, 1], as (node, weight)
+const GAUSS_LEGENDRE: [(f32, f32); 5] = [
+    (0., 0.5688889),
+    (-0.5384693, 0.4786287),
+    (0.5384693, 0.4786287),
+    (-0.9061798, 0.2369269),
+    (0.9061798, 0.2369269),
+];
+
+// pieces per unit of the domain for the length integral and the closest point search
+const PIECES: usize = 16;
+const NEWTON_STEPS: usize = 8;
+// flattening stops splitting here even if the tolerance is not met
+const MAX_SPLITS: usize = 16;
+
+pub trait Curve<T: Scalar> {
+    /**
+     * Parameters of the curve go from zero to the domain
+     */
+    fn domain(&self) -> T;
+
+    /**
+     * Point at the parameter, which is clamped to the domain
+     */
+    fn point_at(&self, t: T) -> Vector<T>;
+
+    /**
+     * Velocity along the curve at the parameter
+     */
+    fn derivative_at(&self, t: T) -> Vector<T>;
+
+    fn second_derivative_at(&self, t: T) -> Vector<T>;
+
+    /**
+     * Length of the curve between two parameters
+     */
+    fn length_between(&self, from: T, to: T) -> T {
+        let half = (to - from) / T::from_f32(2.);
+        let middle = from + half;
+        let speed = GAUSS_LEGENDRE.iter().fold(T::ZERO, |sum, (x, w)| {
+            return sum + self.derivative_at(middle + half * T::from_f32(*x)).size() * T::from_f32(*w);
+        });
+        return speed * half;
+    }
+
+    fn length(&self) -> T {
+        let pieces = sample_count(self.domain());
+        let step = self.domain() / T::from_f32(pieces as f32);
+        return (0..pieces).fold(T::ZERO, |sum, i| {
+            let from = step * T::from_f32(i as f32);
+            return sum + self.length_between(from, from + step);
+        });
+    }
+
+    /**
+     * Parameter of the point of the curve nearest to p: the nearest of evenly spaced samples,
+     * refined by Newton's method
+     */
+    fn closest_parameter(&self, p: &Vector<T>) -> T {
+        let pieces = sample_count(self.domain());
+        let step = self.domain() / T::from_f32(pieces as f32);
+        let distance = |t: T| self.point_at(t).distance_squared(p);
+
+        let mut best = T::ZERO;
+        for i in 1..=pieces {
+            let t = step * T::from_f32(i as f32);
+            if distance(t) < distance(best) {
+                best = t;
+            }
+        }
+
+        // roots of (point - p) . derivative, the derivative of the squared distance
+        let mut t = best;
+        for _ in 0..NEWTON_STEPS {
+            let offset = self.point_at(t) - *p;
+            let d = self.derivative_at(t);
+            let slope = d.dot(&d) + offset.dot(&self.second_derivative_at(t));
+            if slope <= T::ZERO {
+                break;
+            }
+            t = (t - offset.dot(&d) / slope).max(T::ZERO).min(self.domain());
+        }
+        return if distance(t) < distance(best) { t } else { best };
+    }
+
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return self.point_at(self.closest_parameter(p));
+    }
+
+    fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.closest_point(p).distance(p);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadraticBezier<T = f32> {
+    pub start: Vector<T>,
+    pub control: Vector<T>,
+    pub end: Vector<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier<T = f32> {
+    pub start: Vector<T>,
+    pub control1: Vector<T>,
+    pub control2: Vector<T>,
+    pub end: Vector<T>,
+}
+
+impl<T: Scalar> QuadraticBezier<T> {
+    /**
+     * The same curve as a cubic one
+     */
+    pub fn to_cubic(&self) -> CubicBezier<T> {
+        let third = T::from_f32(2. / 3.);
+        return CubicBezier {
+            start: self.start,
+            control1: self.start + (self.control - self.start) * third,
+            control2: self.end + (self.control - self.end) * third,
+            end: self.end,
+        };
+    }
+
+    /**
+     * Smallest frame containing the curve, not just its control points
+     */
+    pub fn to_frame(&self) -> Frame<T> {
+        return self.to_cubic().to_frame();
+    }
+
+    /**
+     * Segments whose vertices lie on the curve and that stay within the tolerance from it
+     */
+    pub fn flatten(&self, tolerance: T) -> Vec<Segment<T>> {
+        return self.to_cubic().flatten(tolerance);
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        self.start += *offset;
+        self.control += *offset;
+        self.end += *offset;
+    }
+}
+
+impl<T: Scalar> Curve<T> for QuadraticBezier<T> {
+    fn domain(&self) -> T {
+        return T::ONE;
+    }
+
+    fn point_at(&self, t: T) -> Vector<T> {
+        let t = t.max(T::ZERO).min(T::ONE);
+        let s = T::ONE - t;
+        return self.start * (s * s) + self.control * (T::from_f32(2.) * s * t) + self.end * (t * t);
+    }
+
+    fn derivative_at(&self, t: T) -> Vector<T> {
+        let t = t.max(T::ZERO).min(T::ONE);
+        let two = T::from_f32(2.);
+        return (self.control - self.start) * (two * (T::ONE - t)) + (self.end - self.control) * (two * t);
+    }
+
+    fn second_derivative_at(&self, _t: T) -> Vector<T> {
+        return (self.start - self.control * T::from_f32(2.) + self.end) * T::from_f32(2.);
+    }
+}
+
+impl<T: Scalar> CubicBezier<T> {
+    /**
+     * The two halves of the curve before and after the parameter (de Casteljau)
+     */
+    pub fn split(&self, t: T) -> (CubicBezier<T>, CubicBezier<T>) {
+        let lerp = |a: Vector<T>, b: Vector<T>| a + (b - a) * t;
+        let ab = lerp(self.start, self.control1);
+        let bc = lerp(self.control1, self.control2);
+        let cd = lerp(self.control2, self.end);
+        let abc = lerp(ab, bc);
+        let bcd = lerp(bc, cd);
+        let middle = lerp(abc, bcd);
+        return (
+            CubicBezier {
+                start: self.start,
+                control1: ab,
+                control2: abc,
+                end: middle,
+            },
+            CubicBezier {
+                start: middle,
+                control1: bcd,
+                control2: cd,
+                end: self.end,
+            },
+        );
+    }
+
+    /**
+     * Smallest frame containing the curve: the ends and the points where
+     * the curve turns back along an axis
+     */
+    pub fn to_frame(&self) -> Frame<T> {
+        let mut frame = Frame::from_points(&[self.start, self.end]);
+        let (p0, p1, p2, p3) = (self.start, self.control1, self.control2, self.end);
+        // the derivative divided by 3 is a t^2 + b t + c, per axis
+        let a = p3 - p0 + (p1 - p2) * T::from_f32(3.);
+        let b = (p0 - p1 * T::from_f32(2.) + p2) * T::from_f32(2.);
+        let c = p1 - p0;
+        for (a, b, c) in [(a.0, b.0, c.0), (a.1, b.1, c.1)] {
+            for t in quadratic_roots(a, b, c).into_iter().flatten() {
+                if t > T::ZERO && t < T::ONE {
+                    frame = frame.union_point(&self.point_at(t));
+                }
+            }
+        }
+        return frame;
+    }
+
+    /**
+     * Segments whose vertices lie on the curve and that stay within the tolerance from it.
+     * A piece is flat enough once its control points are within the tolerance from
+     * its chord, as the curve stays inside their hull. Tolerances below the rounding
+     * error of the control points, including zero, negative and NaN ones, are raised to it.
+     */
+    pub fn flatten(&self, tolerance: T) -> Vec<Segment<T>> {
+        let size = [self.control1, self.control2, self.end]
+            .iter()
+            .map(|p| (*p - self.start).0.abs().max((*p - self.start).1.abs()))
+            .fold(T::ZERO, T::max);
+        // max keeps the other side for NaN
+        let tolerance = T::max(size * T::EPSILON, tolerance);
+        let mut segments = Vec::new();
+        self.flatten_into(tolerance, 0, &mut segments);
+        return segments;
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        self.start += *offset;
+        self.control1 += *offset;
+        self.control2 += *offset;
+        self.end += *offset;
+    }
+
+    fn flatten_into(&self, tolerance: T, depth: usize, segments: &mut Vec<Segment<T>>) {
+        let chord = Segment {
+            location: self.start,
+            vector: self.end - self.start,
+        };
+        let flat = chord.distance_to_point(&self.control1) <= tolerance && chord.distance_to_point(&self.control2) <= tolerance;
+        if flat || depth == MAX_SPLITS {
+            segments.push(chord);
+            return;
+        }
+        let (first, second) = self.split(T::from_f32(0.5));
+        first.flatten_into(tolerance, depth + 1, segments);
+        second.flatten_into(tolerance, depth + 1, segments);
+    }
+}
+
+impl<T: Scalar> Curve<T> for CubicBezier<T> {
+    fn domain(&self) -> T {
+        return T::ONE;
+    }
+
+    fn point_at(&self, t: T) -> Vector<T> {
+        let t = t.max(T::ZERO).min(T::ONE);
+        let s = T::ONE - t;
+        let three = T::from_f32(3.);
+        return self.start * (s * s * s)
+            + self.control1 * (three * s * s * t)
+            + self.control2 * (three * s * t * t)
+            + self.end * (t * t * t);
+    }
+
+    fn derivative_at(&self, t: T) -> Vector<T> {
+        let t = t.max(T::ZERO).min(T::ONE);
+        let s = T::ONE - t;
+        let three = T::from_f32(3.);
+        return (self.control1 - self.start) * (three * s * s)
+            + (self.control2 - self.control1) * (T::from_f32(6.) * s * t)
+            + (self.end - self.control2) * (three * t * t);
+    }
+
+    fn second_derivative_at(&self, t: T) -> Vector<T> {
+        let t = t.max(T::ZERO).min(T::ONE);
+        let six = T::from_f32(6.);
+        return (self.control2 - self.control1 * T::from_f32(2.) + self.start) * (six * (T::ONE - t))
+            + (self.end - self.control2 * T::from_f32(2.) + self.control1) * (six * t);
+    }
+}
+
+/**
+ * Number of pieces a domain is cut into for integration and sampling
+ */
+fn sample_count<T: Scalar>(domain: T) -> usize {
+    return PIECES * (domain.floor().to_f64() as usize).max(1);
+}
+
+/**
+ * Real roots of a t^2 + b t + c, a linear equation when a is zero
+ */
+fn quadratic_roots<T: Scalar>(a: T, b: T, c: T) -> [Option<T>; 2] {
+    if a == T::ZERO {
+        return [(b != T::ZERO).then(|| -c / b), None];
+    }
+    let discriminant = b * b - T::from_f32(4.) * a * c;
+    if discriminant < T::ZERO {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    let two_a = T::from_f32(2.) * a;
+    return [Some((-b - root) / two_a), Some((-b + root) / two_a)];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatmullRomError {
+    TooFewPoints,
+}
+
+impl fmt::Display for CatmullRomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            CatmullRomError::TooFewPoints => write!(f, "spline needs at least 2 points, a loop 3"),
+        };
+    }
+}
+
+/**
+ * Uniform Catmull-Rom spline passing through all of its points, one cubic piece
+ * between each pair of neighbours. Open splines continue past their ends
+ * by mirroring the neighbouring point.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// a saved spline is only its points, the piece count is checked again on load
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "CatmullRomData<T>",
+        into = "CatmullRomData<T>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct CatmullRom<T = f32> {
+    points: Vec<Vector<T>>,
+    looped: bool,
+}
+
+impl<T: Scalar> CatmullRom<T> {
+    /**
+     * Needs at least 2 points, or 3 for a loop
+     */
+    pub fn new(points: &[Vector<T>], looped: bool) -> Result<CatmullRom<T>, CatmullRomError> {
+        if points.len() < 2 || (looped && points.len() < 3) {
+            return Err(CatmullRomError::TooFewPoints);
+        }
+        return Ok(CatmullRom {
+            points: points.to_vec(),
+            looped,
+        });
+    }
+
+    pub fn points(&self) -> &[Vector<T>] {
+        return &self.points;
+    }
+
+    pub fn is_looped(&self) -> bool {
+        return self.looped;
+    }
+
+    pub fn piece_count(&self) -> usize {
+        return if self.looped {
+            self.points.len()
+        } else {
+            self.points.len() - 1
+        };
+    }
+
+    /**
+     * Piece from the point i to the next one, as a Bezier curve
+     */
+    pub fn piece(&self, i: usize) -> CubicBezier<T> {
+        let n = self.points.len();
+        let (p1, p2) = (self.points[i], self.points[(i + 1) % n]);
+        let p0 = if i > 0 || self.looped {
+            self.points[(i + n - 1) % n]
+        } else {
+            p1 * T::from_f32(2.) - p2
+        };
+        let p3 = if i + 2 < n || self.looped {
+            self.points[(i + 2) % n]
+        } else {
+            p2 * T::from_f32(2.) - p1
+        };
+        let sixth = T::from_f32(1. / 6.);
+        return CubicBezier {
+            start: p1,
+            control1: p1 + (p2 - p0) * sixth,
+            control2: p2 - (p3 - p1) * sixth,
+            end: p2,
+        };
+    }
+
+    pub fn pieces(&self) -> impl Iterator<Item = CubicBezier<T>> + '_ {
+        return (0..self.piece_count()).map(|i| self.piece(i));
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        return self.pieces().fold(Frame::empty(), |frame, piece| frame.union(&piece.to_frame()));
+    }
+
+    /**
+     * Segments whose vertices lie on the spline and that stay within the tolerance from it,
+     * see CubicBezier::flatten for tolerances that cannot be met
+     */
+    pub fn flatten(&self, tolerance: T) -> Vec<Segment<T>> {
+        return self.pieces().flat_map(|piece| piece.flatten(tolerance)).collect();
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        for p in self.points.iter_mut() {
+            *p += *offset;
+        }
+    }
+
+    /**
+     * The piece the parameter falls on, and the parameter on that piece
+     */
+    fn locate(&self, t: T) -> (CubicBezier<T>, T) {
+        let t = t.max(T::ZERO).min(self.domain());
+        let i = (t.floor().to_f64() as usize).min(self.piece_count() - 1);
+        return (self.piece(i), t - T::from_f32(i as f32));
+    }
+}
+
+impl<T: Scalar> Curve<T> for CatmullRom<T> {
+    fn domain(&self) -> T {
+        return T::from_f32(self.piece_count() as f32);
+    }
+
+    fn point_at(&self, t: T) -> Vector<T> {
+        let (piece, t) = self.locate(t);
+        return piece.point_at(t);
+    }
+
+    fn derivative_at(&self, t: T) -> Vector<T> {
+        let (piece, t) = self.locate(t);
+        return piece.derivative_at(t);
+    }
+
+    fn second_derivative_at(&self, t: T) -> Vector<T> {
+        let (piece, t) = self.locate(t);
+        return piece.second_derivative_at(t);
+    }
+}
+
+/**
+ * Spline as it is saved, the points and whether they loop
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CatmullRomData<T> {
+    points: Vec<Vector<T>>,
+    looped: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<CatmullRomData<T>> for CatmullRom<T> {
+    type Error = CatmullRomError;
+
+    fn try_from(data: CatmullRomData<T>) -> Result<CatmullRom<T>, CatmullRomError> {
+        return CatmullRom::new(&data.points, data.looped);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<CatmullRom<T>> for CatmullRomData<T> {
+    fn from(spline: CatmullRom<T>) -> CatmullRomData<T> {
+        return CatmullRomData {
+            points: spline.points,
+            looped: spline.looped,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcLengthError {
+    Empty,
+    LengthMismatch,
+    Decreasing,
+}
+
+impl fmt::Display for ArcLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            ArcLengthError::Empty => write!(f, "arc length table has no samples"),
+            ArcLengthError::LengthMismatch => write!(f, "arc length table needs one distance per parameter"),
+            ArcLengthError::Decreasing => write!(f, "arc length table must not decrease"),
+        };
+    }
+}
+
+/**
+ * Table of travelled distances along a curve, for moving along it at constant speed
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// lookups bisect both columns, so a stored table must be sorted before it is used
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "ArcLengthData<T>",
+        into = "ArcLengthData<T>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct ArcLength<T = f32> {
+    parameters: Vec<T>,
+    distances: Vec<T>,
+}
+
+impl<T: Scalar> ArcLength<T> {
+    /**
+     * Samples the curve at the given number of evenly spaced parameters (at least one
+     * interval); more samples make the mapping between distances and parameters smoother
+     */
+    pub fn new(curve: &impl Curve<T>, samples: usize) -> ArcLength<T> {
+        let samples = samples.max(1);
+        let step = curve.domain() / T::from_f32(samples as f32);
+        let mut parameters = Vec::with_capacity(samples + 1);
+        let mut distances = Vec::with_capacity(samples + 1);
+        parameters.push(T::ZERO);
+        distances.push(T::ZERO);
+        for i in 1..=samples {
+            let t = step * T::from_f32(i as f32);
+            let travelled = distances[i - 1] + curve.length_between(parameters[i - 1], t);
+            parameters.push(t);
+            distances.push(travelled);
+        }
+        return ArcLength { parameters, distances };
+    }
+
+    pub fn length(&self) -> T {
+        return self.distances[self.distances.len() - 1];
+    }
+
+    /**
+     * Parameter of the point at the distance along the curve, clamped to the curve
+     */
+    pub fn parameter_at(&self, distance: T) -> T {
+        return interpolate(&self.distances, &self.parameters, distance);
+    }
+
+    /**
+     * Distance travelled along the curve up to the parameter
+     */
+    pub fn distance_at(&self, t: T) -> T {
+        return interpolate(&self.parameters, &self.distances, t);
+    }
+}
+
+/**
+ * The two sampled columns of an ArcLength table
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ArcLengthData<T> {
+    parameters: Vec<T>,
+    distances: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<ArcLengthData<T>> for ArcLength<T> {
+    type Error = ArcLengthError;
+
+    fn try_from(data: ArcLengthData<T>) -> Result<ArcLength<T>, ArcLengthError> {
+        if data.parameters.is_empty() {
+            return Err(ArcLengthError::Empty);
+        }
+        if data.parameters.len() != data.distances.len() {
+            return Err(ArcLengthError::LengthMismatch);
+        }
+        for column in [&data.parameters, &data.distances] {
+            // NaN compares to nothing, so it fails here as well
+            let sorted = column.windows(2).all(|pair| pair[0] <= pair[1]);
+            if !sorted || column.iter().any(|v| v.partial_cmp(v).is_none()) {
+                return Err(ArcLengthError::Decreasing);
+            }
+        }
+        return Ok(ArcLength {
+            parameters: data.parameters,
+            distances: data.distances,
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<ArcLength<T>> for ArcLengthData<T> {
+    fn from(table: ArcLength<T>) -> ArcLengthData<T> {
+        return ArcLengthData {
+            parameters: table.parameters,
+            distances: table.distances,
+        };
+    }
+}
+
+/**
+ * Piecewise linear y(x) through the increasing xs, clamped at both ends
+ */
+fn interpolate<T: Scalar>(xs: &[T], ys: &[T], x: T) -> T {
+    let last = xs.len() - 1;
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[last] {
+        return ys[last];
+    }
+    let i = xs.partition_point(|v| *v <= x).min(last) - 1;
+    let span = xs[i + 1] - xs[i];
+    if span == T::ZERO {
+        return ys[i];
+    }
+    return ys[i] + (ys[i + 1] - ys[i]) * ((x - xs[i]) / span);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::approx::Tolerance;
+    use vector::assert_approx_eq;
+
+    // a quarter of the unit circle, within 3e-4
+    fn quarter() -> CubicBezier {
+        let k = 0.5522848;
+        return CubicBezier {
+            start: Vector(1., 0.),
+            control1: Vector(1., k),
+            control2: Vector(k, 1.),
+            end: Vector(0., 1.),
+        };
+    }
+
+    fn path() -> [Vector; 4] {
+        return [Vector(0., 0.), Vector(2., 1.), Vector(4., 0.), Vector(6., 2.)];
+    }
+
+    #[test]
+    fn quadratic() {
+        let mut arch = QuadraticBezier {
+            start: Vector(0., 0.),
+            control: Vector(1., 2.),
+            end: Vector(2., 0.),
+        };
+
+        assert_eq!(arch.point_at(0.5), Vector(1., 1.));
+        assert_eq!(arch.derivative_at(0.), Vector(2., 4.));
+        assert_eq!(arch.derivative_at(0.5), Vector(2., 0.));
+        assert_eq!(arch.second_derivative_at(0.3), Vector(0., -8.));
+        assert_approx_eq!(arch.to_frame(), Frame { from: Vector(0., 0.), to: Vector(2., 1.) }, Tolerance::Absolute(1e-5));
+
+        // the cubic form is the same curve
+        let cubic = arch.to_cubic();
+        for t in [0., 0.25, 0.6, 1.] {
+            assert_approx_eq!(cubic.point_at(t), arch.point_at(t), Tolerance::Absolute(1e-5));
+        }
+
+        arch.shift(&Vector(1., 1.));
+        assert_eq!(arch.point_at(1.), Vector(3., 1.));
+    }
+
+    #[test]
+    fn cubic() {
+        let c = quarter();
+
+        assert_eq!(c.point_at(0.), Vector(1., 0.));
+        assert_eq!(c.point_at(2.), Vector(0., 1.));
+        assert_approx_eq!(c.point_at(0.5), Vector(0.70710677, 0.70710677), Tolerance::Absolute(1e-3));
+        assert_approx_eq!(c.derivative_at(0.), Vector(0., 3. * 0.5522848));
+
+        // numeric derivatives
+        let h = 1e-3;
+        let numeric = (c.point_at(0.4 + h) - c.point_at(0.4 - h)) / (2. * h);
+        assert_approx_eq!(c.derivative_at(0.4), numeric, Tolerance::Absolute(1e-2));
+        let numeric = (c.derivative_at(0.4 + h) - c.derivative_at(0.4 - h)) / (2. * h);
+        assert_approx_eq!(c.second_derivative_at(0.4), numeric, Tolerance::Absolute(1e-2));
+
+        let (first, second) = c.split(0.3);
+        assert_approx_eq!(first.point_at(0.5), c.point_at(0.15));
+        assert_approx_eq!(second.point_at(0.5), c.point_at(0.65));
+    }
+
+    #[test]
+    fn tight_frame() {
+        let wave = CubicBezier {
+            start: Vector(0., 0.),
+            control1: Vector(1., 3.),
+            control2: Vector(2., -3.),
+            end: Vector(3., 0.),
+        };
+
+        let half = 3f32.sqrt() / 2.;
+        assert_approx_eq!(wave.to_frame(), Frame { from: Vector(0., -half), to: Vector(3., half) });
+        assert_eq!(quarter().to_frame(), Frame { from: Vector(0., 0.), to: Vector(1., 1.) });
+    }
+
+    #[test]
+    fn length() {
+        let line = CubicBezier {
+            start: Vector(0., 0.),
+            control1: Vector(0., 0.),
+            control2: Vector(3., 4.),
+            end: Vector(3., 4.),
+        };
+
+        assert_approx_eq!(line.length(), 5., Tolerance::Absolute(1e-5));
+        assert_approx_eq!(quarter().length(), core::f32::consts::FRAC_PI_2, Tolerance::Absolute(1e-3));
+    }
+
+    #[test]
+    fn arc_length() {
+        // a straight line that starts and ends slowly
+        let line = CubicBezier {
+            start: Vector(0., 0.),
+            control1: Vector(0., 0.),
+            control2: Vector(4., 0.),
+            end: Vector(4., 0.),
+        };
+        let table = ArcLength::new(&line, 64);
+
+        assert_approx_eq!(table.length(), 4., Tolerance::Absolute(1e-5));
+        for distance in [0., 0.5, 1., 2., 3.7, 4.] {
+            let t = table.parameter_at(distance);
+            assert_approx_eq!(line.point_at(t).0, distance, Tolerance::Absolute(1e-2));
+            assert_approx_eq!(table.distance_at(t), distance, Tolerance::Absolute(1e-4));
+        }
+        assert_eq!(table.parameter_at(-1.), 0.);
+        assert_eq!(table.parameter_at(10.), 1.);
+    }
+
+    #[test]
+    fn flatten() {
+        let c = quarter();
+
+        for tolerance in [0.1, 1e-2, 1e-3] {
+            let segments = c.flatten(tolerance);
+            assert_eq!(segments[0].location, c.start);
+            assert_approx_eq!(segments[segments.len() - 1].end(), c.end);
+            for pair in segments.windows(2) {
+                assert_eq!(pair[0].end(), pair[1].location);
+            }
+            // the middle of every segment is close to the curve
+            for s in &segments {
+                let middle = s.location + s.vector / 2.;
+                assert!(c.distance_to_point(&middle) <= tolerance);
+            }
+        }
+        assert!(c.flatten(1e-3).len() > c.flatten(0.1).len());
+    }
+
+    #[test]
+    fn flatten_without_tolerance() {
+        let c = quarter();
+        let finest = c.flatten(f32::EPSILON);
+
+        assert!(finest.len() < 1 << MAX_SPLITS);
+        for tolerance in [0., -1., f32::NAN, f32::NEG_INFINITY] {
+            assert_eq!(c.flatten(tolerance), finest);
+        }
+        let point = CubicBezier {
+            start: Vector(1., 2.),
+            control1: Vector(1., 2.),
+            control2: Vector(1., 2.),
+            end: Vector(1., 2.),
+        };
+        assert_eq!(point.flatten(0.).len(), 1);
+        let spline = CatmullRom::new(&path(), false).unwrap();
+        assert_eq!(spline.flatten(f32::NAN), spline.flatten(0.));
+    }
+
+    #[test]
+    fn closest_point() {
+        let c = quarter();
+
+        assert_approx_eq!(c.closest_point(&Vector(2., 2.)), Vector(0.70710677, 0.70710677), Tolerance::Absolute(1e-3));
+        assert_approx_eq!(c.closest_point(&Vector(0.5, 0.2)), Vector(0.9284767, 0.37139067), Tolerance::Absolute(1e-3));
+        // past the ends
+        assert_eq!(c.closest_point(&Vector(3., -1.)), Vector(1., 0.));
+        assert_approx_eq!(c.distance_to_point(&Vector(-1., 1.)), 1.);
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let spline = CatmullRom::new(&path(), false).unwrap();
+
+        assert_eq!(spline.piece_count(), 3);
+        assert_eq!(spline.domain(), 3.);
+        for (i, p) in path().iter().enumerate() {
+            assert_approx_eq!(spline.point_at(i as f32), *p);
+        }
+        // smooth where the pieces meet
+        assert_approx_eq!(spline.derivative_at(0.9999), spline.derivative_at(1.), Tolerance::Absolute(1e-2));
+        assert_approx_eq!(spline.derivative_at(2.), Vector(2., 0.5), Tolerance::Absolute(1e-5));
+
+        let frame = spline.to_frame();
+        assert!(path().iter().all(|p| frame.contains(p)));
+        // the spline swings past the middle points
+        assert!(frame.to.1 > 2. || frame.from.1 < 0.);
+
+        assert_eq!(CatmullRom::new(&path()[..1], false), Err(CatmullRomError::TooFewPoints));
+        assert_eq!(CatmullRom::new(&path()[..2], true), Err(CatmullRomError::TooFewPoints));
+    }
+
+    #[test]
+    fn catmull_rom_loop() {
+        let square = [Vector(0., 0.), Vector(2., 0.), Vector(2., 2.), Vector(0., 2.)];
+        let mut ring = CatmullRom::new(&square, true).unwrap();
+
+        assert_eq!(ring.piece_count(), 4);
+        assert_approx_eq!(ring.point_at(4.), square[0]);
+        assert_approx_eq!(ring.derivative_at(0.), ring.derivative_at(4.));
+
+        let segments = ring.flatten(1e-2);
+        assert_approx_eq!(segments[segments.len() - 1].end(), segments[0].location);
+        let table = ArcLength::new(&ring, 128);
+        assert_approx_eq!(table.length(), ring.length(), Tolerance::Absolute(1e-3));
+        assert_approx_eq!(ring.closest_point(&Vector(1., -1.)), ring.point_at(0.5), Tolerance::Absolute(1e-3));
+
+        ring.shift(&Vector(1., 0.));
+        assert_eq!(ring.points()[0], Vector(1., 0.));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn catmull_rom_serde() {
+        let spline = CatmullRom::new(&path(), false).unwrap();
+        let json = serde_json::to_string(&spline).unwrap();
+        assert_eq!(serde_json::from_str::<CatmullRom>(&json).unwrap(), spline);
+
+        let error = serde_json::from_str::<CatmullRom>(r#"{"points":[[0,0]],"looped":false}"#).unwrap_err();
+        assert!(error.to_string().contains("at least 2 points"));
+        assert!(serde_json::from_str::<CatmullRom>(r#"{"points":[],"looped":false}"#).is_err());
+        assert!(serde_json::from_str::<CatmullRom>(r#"{"points":[[0,0],[1,0]],"looped":true}"#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn arc_length_serde() {
+        let table = ArcLength::new(&quarter(), 8);
+        let json = serde_json::to_string(&table).unwrap();
+        assert_eq!(serde_json::from_str::<ArcLength>(&json).unwrap(), table);
+
+        let error = serde_json::from_str::<ArcLength>(r#"{"parameters":[],"distances":[]}"#).unwrap_err();
+        assert!(error.to_string().contains("no samples"));
+        let error = serde_json::from_str::<ArcLength>(r#"{"parameters":[0,1],"distances":[0]}"#).unwrap_err();
+        assert!(error.to_string().contains("one distance per parameter"));
+        let error = serde_json::from_str::<ArcLength>(r#"{"parameters":[0,1],"distances":[1,0]}"#).unwrap_err();
+        assert!(error.to_string().contains("must not decrease"));
+        assert!(serde_json::from_str::<ArcLength>(r#"{"parameters":[1,0],"distances":[0,1]}"#).is_err());
+    }
+}
@@ -1,62 +1,247 @@
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
 use vector::vector::Vector;
+
+use crate::ray::{convex_raycast, Ray, RayHit};
 /**
  * Frame modeling the rectungle boundary of shape 
  */
 
 
-#[derive(Debug, PartialEq)]
-pub struct Frame {
-    pub from: Vector,
-    pub to: Vector,
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frame<T = f32> {
+    pub from: Vector<T>,
+    pub to: Vector<T>,
 }
 
 
-impl Frame {
+impl<T: Scalar> Frame<T> {
 
-    pub fn center(&self) -> Vector {
-        return Vector(
-            (self.from.0 + self.to.0) / 2.0,
-            (self.from.1 + self.to.1) / 2.0,
-        );
+    /**
+     * Frame containing nothing, the starting point for union
+     */
+    pub fn empty() -> Frame<T> {
+        return Frame {
+            from: Vector(T::MAX, T::MAX),
+            to: Vector(-T::MAX, -T::MAX),
+        };
     }
 
-    pub fn dementions(&self) -> Vector {
-        return Vector(self.to.0 - self.from.0, self.to.1 - self.from.1);
+    /**
+     * Frame containing everything. Its dementions, area and perimeter overflow the scalar:
+     * infinity for floats, MAX for the saturating fixed-point types.
+     */
+    pub fn infinite() -> Frame<T> {
+        return Frame {
+            from: Vector(-T::MAX, -T::MAX),
+            to: Vector(T::MAX, T::MAX),
+        };
     }
 
-    pub fn is_intercected(&self, other: &Frame) -> bool {
-        if self.to.0 < other.from.0 {
-            return false;
-        } else if self.from.0 > other.to.0 {
-            return false;
-        } else if self.to.1 < other.from.1 {
-            return false;
-        } else if self.from.1 > other.to.1 {
+    /**
+     * Smallest frame containing all the points, empty if there are none
+     */
+    pub fn from_points(points: &[Vector<T>]) -> Frame<T> {
+        return points.iter().fold(Frame::empty(), |f, p| f.union_point(p));
+    }
+
+    /**
+     * True if from is past to on some axis, like for Frame::empty()
+     */
+    pub fn is_empty(&self) -> bool {
+        return self.from.0 > self.to.0 || self.from.1 > self.to.1;
+    }
+
+    /**
+     * Frame with from and to swapped where they are inverted, for frames given by any two corners
+     */
+    pub fn normalize(&self) -> Frame<T> {
+        return Frame {
+            from: Vector(self.from.0.min(self.to.0), self.from.1.min(self.to.1)),
+            to: Vector(self.from.0.max(self.to.0), self.from.1.max(self.to.1)),
+        };
+    }
+
+    pub fn center(&self) -> Vector<T> {
+        return (self.from + self.to) / T::from_f32(2.0);
+    }
+
+    pub fn dementions(&self) -> Vector<T> {
+        return self.to - self.from;
+    }
+
+    /**
+     * Zero for an empty frame
+     */
+    pub fn area(&self) -> T {
+        if self.is_empty() {
+            return T::ZERO;
+        }
+        let d = self.dementions();
+        return d.0 * d.1;
+    }
+
+    /**
+     * Cost of a node in bounding volume trees (surface area heuristic), zero for an empty frame
+     */
+    pub fn perimeter(&self) -> T {
+        if self.is_empty() {
+            return T::ZERO;
+        }
+        let d = self.dementions();
+        return (d.0 + d.1) * T::from_f32(2.);
+    }
+
+    /**
+     * Smallest frame containing both frames
+     */
+    pub fn union(&self, other: &Frame<T>) -> Frame<T> {
+        return Frame {
+            from: Vector(self.from.0.min(other.from.0), self.from.1.min(other.from.1)),
+            to: Vector(self.to.0.max(other.to.0), self.to.1.max(other.to.1)),
+        };
+    }
+
+    /**
+     * Smallest frame containing the frame and the point
+     */
+    pub fn union_point(&self, p: &Vector<T>) -> Frame<T> {
+        return self.union(&Frame { from: *p, to: *p });
+    }
+
+    /**
+     * True if the point is inside or on the boundary
+     */
+    pub fn contains(&self, p: &Vector<T>) -> bool {
+        return self.from.0 <= p.0 && p.0 <= self.to.0 && self.from.1 <= p.1 && p.1 <= self.to.1;
+    }
+
+    /**
+     * True if the other frame is inside this one, boundaries may touch.
+     * An empty frame is inside every frame.
+     */
+    pub fn contains_frame(&self, other: &Frame<T>) -> bool {
+        return other.is_empty()
+            || (self.from.0 <= other.from.0
+                && self.from.1 <= other.from.1
+                && other.to.0 <= self.to.0
+                && other.to.1 <= self.to.1);
+    }
+
+    /**
+     * Frame grown by the margin on every side, a negative margin shrinks it
+     */
+    pub fn expand(&self, margin: T) -> Frame<T> {
+        let m = Vector(margin, margin);
+        return Frame {
+            from: self.from - m,
+            to: self.to + m,
+        };
+    }
+
+    /**
+     * Frame covering every position of this frame while it moves by the offset,
+     * like a velocity multiplied by the time step
+     */
+    pub fn sweep(&self, offset: &Vector<T>) -> Frame<T> {
+        return self.union(&Frame {
+            from: self.from + *offset,
+            to: self.to + *offset,
+        });
+    }
+
+    /**
+     * True if the frames overlap or touch, never for an empty frame
+     */
+    pub fn is_intercected(&self, other: &Frame<T>) -> bool {
+        if self.is_empty() || other.is_empty() {
             return false;
-        } else {
-            return true;
         }
+        return !(self.to.0 < other.from.0
+            || self.from.0 > other.to.0
+            || self.to.1 < other.from.1
+            || self.from.1 > other.to.1);
     }
     
-    pub fn intercect(&self, other: &Frame) -> Option<Frame> {
+    pub fn intercect(&self, other: &Frame<T>) -> Option<Frame<T>> {
         if self.is_intercected(other) {
             return Option::Some(Frame {
                 from: Vector(
-                    f32::max(self.from.0, other.from.0),
-                    f32::max(self.from.1, other.from.1),
+                    T::max(self.from.0, other.from.0),
+                    T::max(self.from.1, other.from.1),
                 ),
-                to: Vector(f32::min(self.to.0, other.to.0), f32::min(self.to.1, other.to.1)),
+                to: Vector(T::min(self.to.0, other.to.0), T::min(self.to.1, other.to.1)),
             });
         } else {
             return Option::None;
         }
     }
 
+    /**
+     * Bounding frame of this frame after the transform
+     */
+    pub fn transform(&self, t: &Transform2D<T>) -> Frame<T> {
+        let half = self.dementions() * T::from_f32(0.5);
+        let center = t.apply_point(&self.center());
+        let extent = Vector(
+            t.x_axis.0.abs() * half.0 + t.y_axis.0.abs() * half.1,
+            t.x_axis.1.abs() * half.0 + t.y_axis.1.abs() * half.1,
+        );
+        return Frame {
+            from: center - extent,
+            to: center + extent,
+        };
+    }
+
+    /**
+     * Point of the frame nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Vector(p.0.max(self.from.0).min(self.to.0), p.1.max(self.from.1).min(self.to.1));
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.closest_point(p).distance(p);
+    }
+
+    /**
+     * Distance to the boundary, negative inside
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        let half = self.dementions() * T::from_f32(0.5);
+        let d = *p - self.center();
+        let q = Vector(d.0.abs() - half.0, d.1.abs() - half.1);
+        let outside = Vector(q.0.max(T::ZERO), q.1.max(T::ZERO)).size();
+        let inside = q.0.max(q.1).min(T::ZERO);
+        return outside + inside;
+    }
+
+    /**
+     * Ray against the closed frame, a ray starting on the boundary hits it at distance zero
+     */
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        let corners = [self.from, Vector(self.to.0, self.from.1), self.to, Vector(self.from.0, self.to.1)];
+        return convex_raycast(&corners, ray);
+    }
+}
+
+impl<T> ApproxEq for Frame<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Frame<T>, tolerance: Tolerance<T>) -> bool {
+        return self.from.approx_eq(&other.from, tolerance) && self.to.approx_eq(&other.to, tolerance);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use vector::fixed::Fix16;
 
     #[test]
     fn dementions() {
@@ -104,4 +289,167 @@ mod tests {
         }));
         
     }
+
+    #[test]
+    fn transform() {
+        let f = Frame{
+                from: Vector(0.,0.),
+                to: Vector(2.,2.),
+        };
+        let t = Transform2D::from_trs(Vector(1., 1.), std::f32::consts::FRAC_PI_4, Vector(1., 1.));
+        let r = f.transform(&t);
+        let h = 2f32.sqrt();
+
+        assert!((r.center() - Vector(1., 1. + h)).size() < 1e-5);
+        assert!((r.dementions() - Vector(2. * h, 2. * h)).size() < 1e-5);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let f = Frame{
+                from: Vector(0.,1.),
+                to: Vector(2.,3.),
+        };
+
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, r#"{"from":[0.0,1.0],"to":[2.0,3.0]}"#);
+        assert_eq!(serde_json::from_str::<Frame>(&json).unwrap(), f);
+
+        let ron = ron::to_string(&f).unwrap();
+        assert_eq!(ron::from_str::<Frame>(&ron).unwrap(), f);
+    }
+
+    #[test]
+    fn empty_and_infinite() {
+        let f = Frame {
+            from: Vector(0., 0.),
+            to: Vector(2., 2.),
+        };
+        let empty = Frame::empty();
+
+        assert!(empty.is_empty());
+        assert!(!f.is_empty());
+        assert_eq!(empty.union(&f), f);
+        assert_eq!(empty.area(), 0.);
+        assert_eq!(empty.perimeter(), 0.);
+        assert!(!empty.is_intercected(&f));
+        assert!(!empty.contains(&Vector(0., 0.)));
+
+        let infinite = Frame::infinite();
+        assert_eq!(infinite.union(&f), infinite);
+        assert!(infinite.contains_frame(&f));
+        assert!(infinite.contains(&Vector(-1e30, 1e30)));
+        assert_eq!(Frame::<f32>::from_points(&[]), empty);
+        assert_eq!(infinite.area(), f32::INFINITY);
+    }
+
+    #[test]
+    fn empty_and_infinite_fixed_point() {
+        let f = Frame {
+            from: Vector(Fix16::from_int(-1), Fix16::ZERO),
+            to: Vector(Fix16::from_int(2), Fix16::from_int(3)),
+        };
+        let empty = Frame::empty();
+        let infinite = Frame::infinite();
+
+        assert_eq!(empty.union(&f), f);
+        assert_eq!(empty.area(), Fix16::ZERO);
+        assert_eq!(empty.perimeter(), Fix16::ZERO);
+        assert!(empty.expand(Fix16::ONE).is_empty());
+        assert_eq!(Frame::from_points(&[f.from, f.to]), f);
+
+        // sizes saturate instead of wrapping around to negative values
+        assert_eq!(infinite.dementions(), Vector(Fix16::MAX, Fix16::MAX));
+        assert_eq!(infinite.area(), Fix16::MAX);
+        assert_eq!(infinite.perimeter(), Fix16::MAX);
+        assert_eq!(infinite.center(), Vector(Fix16::ZERO, Fix16::ZERO));
+        assert_eq!(infinite.union(&f), infinite);
+        assert!(infinite.contains_frame(&f));
+        assert_eq!(infinite.expand(Fix16::ONE), infinite);
+        assert!(!empty.is_intercected(&infinite));
+    }
+
+    #[test]
+    fn empty_intersects_nothing() {
+        let empty = Frame::<f32>::empty();
+        let infinite = Frame::infinite();
+
+        assert!(!empty.is_intercected(&infinite));
+        assert!(!infinite.is_intercected(&empty));
+        assert!(!empty.is_intercected(&empty));
+        assert_eq!(empty.intercect(&infinite), None);
+        assert!(infinite.is_intercected(&infinite));
+    }
+
+    #[test]
+    fn union_and_containment() {
+        let f = Frame::from_points(&[Vector(1., 3.), Vector(-1., 0.), Vector(2., 1.)]);
+        assert_eq!(f, Frame { from: Vector(-1., 0.), to: Vector(2., 3.) });
+
+        assert!(f.contains(&Vector(2., 3.)));
+        assert!(!f.contains(&Vector(2.1, 3.)));
+        assert!(f.contains_frame(&Frame { from: Vector(0., 0.), to: Vector(2., 1.) }));
+        assert!(!f.contains_frame(&Frame { from: Vector(0., 0.), to: Vector(3., 1.) }));
+        assert!(f.contains_frame(&Frame::empty()));
+
+        let g = Frame { from: Vector(5., 5.), to: Vector(6., 7.) };
+        assert_eq!(f.union(&g), Frame { from: Vector(-1., 0.), to: Vector(6., 7.) });
+        assert_eq!(f.union_point(&Vector(0., -2.)), Frame { from: Vector(-1., -2.), to: Vector(2., 3.) });
+    }
+
+    #[test]
+    fn expand_and_sweep() {
+        let f = Frame { from: Vector(0., 0.), to: Vector(2., 1.) };
+
+        assert_eq!(f.area(), 2.);
+        assert_eq!(f.perimeter(), 6.);
+        assert_eq!(f.expand(1.), Frame { from: Vector(-1., -1.), to: Vector(3., 2.) });
+        assert!(f.expand(-1.).is_empty());
+        assert_eq!(f.sweep(&Vector(3., -1.)), Frame { from: Vector(0., -1.), to: Vector(5., 1.) });
+        assert_eq!(f.sweep(&Vector(0., 0.)), f);
+    }
+
+    #[test]
+    fn normalize() {
+        let inverted = Frame { from: Vector(2., 0.), to: Vector(0., 3.) };
+
+        assert!(inverted.is_empty());
+        assert_eq!(inverted.normalize(), Frame { from: Vector(0., 0.), to: Vector(2., 3.) });
+        assert_eq!(inverted.normalize().normalize(), inverted.normalize());
+    }
+
+    #[test]
+    fn raycast() {
+        let f = Frame {
+            from: Vector(0., 0.),
+            to: Vector(2., 2.),
+        };
+
+        let hit = f.raycast(&Ray::new(Vector(1., -3.), Vector(0., 1.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 3., point: Vector(1., 0.), normal: Vector(0., -1.) });
+        assert_eq!(f.raycast(&Ray::new(Vector(1., -3.), Vector(0., 1.)).with_max_distance(2.)), None);
+        assert_eq!(f.raycast(&Ray::new(Vector(3., -3.), Vector(0., 1.))), None);
+
+        // starting on the boundary and inside
+        let hit = f.raycast(&Ray::new(Vector(0., 1.), Vector(1., 0.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 0., point: Vector(0., 1.), normal: Vector(-1., 0.) });
+        let hit = f.raycast(&Ray::new(Vector(1., 1.), Vector(0., -1.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 0., point: Vector(1., 1.), normal: Vector(0., 1.) });
+    }
+
+    #[test]
+    fn distance() {
+        let f = Frame {
+            from: Vector(0., 0.),
+            to: Vector(2., 4.),
+        };
+
+        assert_eq!(f.closest_point(&Vector(-1., 5.)), Vector(0., 4.));
+        assert_eq!(f.closest_point(&Vector(1., 1.)), Vector(1., 1.));
+        assert_eq!(f.distance_to_point(&Vector(5., 8.)), 5.);
+        assert_eq!(f.signed_distance(&Vector(5., 8.)), 5.);
+        assert_eq!(f.signed_distance(&Vector(1.5, 2.)), -0.5);
+        assert_eq!(f.signed_distance(&Vector(2., 2.)), 0.);
+    }
 }
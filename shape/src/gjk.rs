@@ -0,0 +1,312 @@
+/*!
+ * GJK and EPA for any pair of convex shapes.
+ *
+ * Both algorithms only need the furthest point of each shape in a given
+ * direction (its support map). They work on the Minkowski difference A - B:
+ * the shapes intersect iff it contains the origin, their distance is the
+ * distance of the origin to it, and the penetration is the shortest way
+ * from the origin out of it. Curved shapes are approximated by the polygon
+ * of the support points found, up to a small tolerance.
+ */
+
+use alloc::vec::Vec;
+
+use vector::scalar::Scalar;
+use vector::vector::{Orientation, Vector};
+
+use crate::contact::Contact;
+
+// concentric circles are the worst case, every edge of the polytope gets refined
+const MAX_ITERATIONS: usize = 128;
+
+/**
+ * Convex shape described by its furthest point in every direction
+ */
+pub trait SupportMap<T: Scalar> {
+    /**
+     * Point of the shape furthest along the direction, any of them on a tie.
+     * The direction does not have to be of unit length.
+     */
+    fn support(&self, direction: &Vector<T>) -> Vector<T>;
+}
+
+impl<T: Scalar> SupportMap<T> for Vector<T> {
+    fn support(&self, _direction: &Vector<T>) -> Vector<T> {
+        return *self;
+    }
+}
+
+/**
+ * Vertex of the Minkowski difference, together with the point of the first shape it came from
+ */
+#[derive(Debug, Clone, Copy)]
+struct Vertex<T> {
+    w: Vector<T>,
+    a: Vector<T>,
+}
+
+fn vertex<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>, direction: &Vector<T>) -> Vertex<T> {
+    let pa = a.support(direction);
+    let pb = b.support(&-*direction);
+    return Vertex { w: pa - pb, a: pa };
+}
+
+/**
+ * True if the shapes overlap or touch
+ */
+pub fn intersects<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> bool {
+    return gjk(a, b).1 == T::ZERO;
+}
+
+/**
+ * Smallest gap between the shapes, zero if they overlap or touch
+ */
+pub fn distance<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> T {
+    return gjk(a, b).1;
+}
+
+/**
+ * Normal and depth of the overlap with a single contact point halfway between the surfaces,
+ * None if the shapes do not overlap. Moving b by contact.separation() separates them.
+ */
+pub fn penetration<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> Option<Contact<T>> {
+    let (simplex, gap) = gjk(a, b);
+    if gap > T::ZERO {
+        return None;
+    }
+    return epa(a, b, simplex);
+}
+
+/**
+ * Distance of the origin to the Minkowski difference, with the simplex
+ * the search ended on. A simplex of three vertices contains the origin.
+ */
+fn gjk<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> (Vec<Vertex<T>>, T) {
+    let mut simplex = Vec::with_capacity(3);
+    simplex.push(vertex(a, b, &Vector(T::ONE, T::ZERO)));
+    let mut v = simplex[0].w;
+
+    for _ in 0..MAX_ITERATIONS {
+        if v.size_squared() == T::ZERO || simplex.len() == 3 {
+            return (simplex, T::ZERO);
+        }
+
+        let next = vertex(a, b, &-v);
+        // no point of the difference is closer to the origin than v
+        let progress = v.size_squared() - v.dot(&next.w);
+        if progress <= tolerance(v.size_squared()) || simplex.iter().any(|s| s.w == next.w) {
+            break;
+        }
+        simplex.push(next);
+        v = reduce(&mut simplex);
+    }
+    return (simplex, v.size());
+}
+
+/**
+ * Point of the simplex closest to the origin, the simplex is reduced
+ * to the smallest subset it lies on
+ */
+fn reduce<T: Scalar>(simplex: &mut Vec<Vertex<T>>) -> Vector<T> {
+    let zero = Vector(T::ZERO, T::ZERO);
+    if simplex.len() == 3 {
+        let (p, q, r) = (simplex[0].w, simplex[1].w, simplex[2].w);
+        let o1 = Vector::orientation(&p, &q, &zero);
+        let o2 = Vector::orientation(&q, &r, &zero);
+        let o3 = Vector::orientation(&r, &p, &zero);
+        // inside (or on the boundary) when no edge turns away from the origin, whatever the winding
+        let turns = [o1, o2, o3];
+        let inside = turns.iter().all(|o| *o != Orientation::Clockwise)
+            || turns.iter().all(|o| *o != Orientation::Counterclockwise);
+        if inside && Vector::orientation(&p, &q, &r) != Orientation::Collinear {
+            return zero;
+        }
+
+        // the nearest of the three edges
+        let mut best: Option<(Vec<Vertex<T>>, Vector<T>)> = None;
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            let mut edge = alloc::vec![simplex[i], simplex[j]];
+            let v = reduce(&mut edge);
+            if best.as_ref().is_none_or(|(_, b)| v.size_squared() < b.size_squared()) {
+                best = Some((edge, v));
+            }
+        }
+        let (edge, v) = best.unwrap();
+        *simplex = edge;
+        return v;
+    }
+
+    if simplex.len() == 2 {
+        let (p, q) = (simplex[0].w, simplex[1].w);
+        let pq = q - p;
+        let length_squared = pq.size_squared();
+        let t = if length_squared == T::ZERO {
+            T::ZERO
+        } else {
+            (-p.dot(&pq) / length_squared).max(T::ZERO).min(T::ONE)
+        };
+        if t == T::ZERO {
+            simplex.truncate(1);
+            return p;
+        }
+        if t == T::ONE {
+            simplex.remove(0);
+            return q;
+        }
+        return p + pq * t;
+    }
+
+    return simplex[0].w;
+}
+
+/**
+ * Expands the simplex containing the origin towards the boundary of the difference,
+ * until the edge nearest to the origin is (close to) on the boundary
+ */
+fn epa<T: Scalar>(a: &impl SupportMap<T>, b: &impl SupportMap<T>, simplex: Vec<Vertex<T>>) -> Option<Contact<T>> {
+    let mut polytope = simplex;
+    match polytope.len() {
+        // a single support point at the origin, it is on the boundary
+        1 => return None,
+        // the origin is on a segment between two support points, look at both sides of it
+        2 => {
+            let (p, q) = (polytope[0], polytope[1]);
+            let side = (q.w - p.w).perp_right();
+            let right = vertex(a, b, &side);
+            let left = vertex(a, b, &-side);
+            if Vector::orientation(&p.w, &q.w, &right.w) == Orientation::Collinear
+                || Vector::orientation(&p.w, &q.w, &left.w) == Orientation::Collinear
+            {
+                return None;
+            }
+            polytope = alloc::vec![p, right, q, left];
+        }
+        _ => match Vector::orientation(&polytope[0].w, &polytope[1].w, &polytope[2].w) {
+            Orientation::Collinear => return None,
+            Orientation::Clockwise => polytope.swap(1, 2),
+            Orientation::Counterclockwise => {}
+        },
+    }
+
+    let mut nearest = (0, Vector(T::ZERO, T::ZERO), T::ZERO);
+    for _ in 0..MAX_ITERATIONS {
+        nearest = nearest_edge(&polytope);
+        let (i, normal, depth) = nearest;
+        let next = vertex(a, b, &normal);
+        if next.w.dot(&normal) - depth <= tolerance(depth.max(T::ONE)) {
+            break;
+        }
+        polytope.insert(i + 1, next);
+    }
+
+    let (i, normal, depth) = nearest;
+    if depth <= T::ZERO {
+        return None;
+    }
+    let (p, q) = (polytope[i], polytope[(i + 1) % polytope.len()]);
+    // the origin projects to normal * depth, take the same position between the points of a
+    let edge = q.w - p.w;
+    let t = ((normal * depth - p.w).dot(&edge) / edge.size_squared()).max(T::ZERO).min(T::ONE);
+    let on_a = p.a + (q.a - p.a) * t;
+    let point = on_a - normal * (depth / T::from_f32(2.));
+    return Some(Contact {
+        normal,
+        depth,
+        points: [point; 2],
+        point_count: 1,
+    });
+}
+
+/**
+ * Index of the polytope edge nearest to the origin, its outward normal and distance
+ */
+fn nearest_edge<T: Scalar>(polytope: &[Vertex<T>]) -> (usize, Vector<T>, T) {
+    let mut best: Option<(usize, Vector<T>, T)> = None;
+    for i in 0..polytope.len() {
+        let p = polytope[i].w;
+        let q = polytope[(i + 1) % polytope.len()].w;
+        let mut normal = (q - p).perp_right();
+        normal.normalize();
+        if normal.size() == T::ZERO {
+            continue;
+        }
+        let distance = normal.dot(&p);
+        if best.is_none_or(|(_, _, d)| distance < d) {
+            best = Some((i, normal, distance));
+        }
+    }
+    return best.unwrap();
+}
+
+/**
+ * Convergence threshold for a quantity of the given magnitude
+ */
+fn tolerance<T: Scalar>(magnitude: T) -> T {
+    return magnitude * T::EPSILON.sqrt();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::Circle;
+    use vector::approx::Tolerance;
+    use vector::assert_approx_eq;
+
+    struct Square(Vector);
+
+    impl SupportMap<f32> for Square {
+        fn support(&self, d: &Vector) -> Vector {
+            let corner = Vector(if d.0 < 0. { -1. } else { 1. }, if d.1 < 0. { -1. } else { 1. });
+            return self.0 + corner;
+        }
+    }
+
+    #[test]
+    fn squares() {
+        let a = Square(Vector(0., 0.));
+
+        assert!(intersects(&a, &Square(Vector(1.5, 1.))));
+        assert!(!intersects(&a, &Square(Vector(3., 1.))));
+        assert_eq!(distance(&a, &Square(Vector(5., 1.))), 3.);
+        assert_approx_eq!(distance(&a, &Square(Vector(5., 6.))), 5.);
+
+        let c = penetration(&a, &Square(Vector(1.5, 1.))).unwrap();
+        assert_approx_eq!(c.normal, Vector(1., 0.));
+        assert_approx_eq!(c.depth, 0.5);
+        assert_eq!(penetration(&a, &Square(Vector(3., 1.))), None);
+    }
+
+    #[test]
+    fn points() {
+        let p = Vector(1., 2.);
+
+        assert!(intersects(&p, &p));
+        assert_eq!(distance(&p, &Vector(4., 6.)), 5.);
+        // a point has no inside
+        assert_eq!(penetration(&p, &p), None);
+        assert!(intersects(&Square(Vector(0., 0.)), &Vector(0.5, 0.5)));
+    }
+
+    #[test]
+    fn circles() {
+        let a = Circle {
+            location: Vector(0., 0.),
+            radius: 1.,
+        };
+        let b = Circle {
+            location: Vector(1.5, 0.),
+            radius: 1.,
+        };
+
+        let c = penetration(&a, &b).unwrap();
+        assert_approx_eq!(c.normal, Vector(1., 0.), Tolerance::Absolute(1e-3));
+        assert_approx_eq!(c.depth, 0.5, Tolerance::Absolute(1e-3));
+        assert_approx_eq!(c.points[0], Vector(0.75, 0.), Tolerance::Absolute(1e-3));
+
+        let far = Circle {
+            location: Vector(3., 4.),
+            radius: 1.,
+        };
+        assert_approx_eq!(distance(&a, &far), 3., Tolerance::Absolute(1e-4));
+    }
+}
@@ -0,0 +1,291 @@
+/*!
+ * Convex hull of point sets, for collision hulls built from sprite outlines or debris.
+ */
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use vector::scalar::Scalar;
+use vector::vector::{Orientation, Vector};
+
+use crate::polygon::{Polygon, PolygonError, MAX_POLYGON_VERTICES};
+
+/**
+ * Convex hull by Andrew's monotone chain, counter-clockwise from the lowest-leftmost point.
+ * Duplicates and points in the middle of hull edges are dropped, so every vertex is a
+ * strict left turn. Fewer than three points come back when the input has no area:
+ * none for no points, one for coincident points, the two ends for collinear points.
+ * Points with NaN or infinite coordinates are skipped.
+ */
+pub fn convex_hull<T: Scalar>(points: &[Vector<T>]) -> Vec<Vector<T>> {
+    let finite = |v: &T| -T::MAX <= *v && *v <= T::MAX;
+    let mut sorted: Vec<Vector<T>> = points.iter().filter(|p| finite(&p.0) && finite(&p.1)).copied().collect();
+    // finite coordinates are totally ordered, partial_cmp never gives None
+    sorted.sort_by(|a, b| {
+        return a.0
+            .partial_cmp(&b.0)
+            .unwrap_or(Ordering::Equal)
+            .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // lower chain left to right, then upper chain back, both starting from the last vertex found
+    let mut hull: Vec<Vector<T>> = Vec::with_capacity(sorted.len() + 1);
+    for p in &sorted {
+        push_turning_left(&mut hull, 0, p);
+    }
+    let lower = hull.len() - 1;
+    for p in sorted.iter().rev().skip(1) {
+        push_turning_left(&mut hull, lower, p);
+    }
+    // the upper chain ends on the first point again
+    hull.pop();
+    if hull.len() < 3 {
+        // every point on one line, the hull is the segment between the ends
+        return alloc::vec![sorted[0], sorted[sorted.len() - 1]];
+    }
+    return hull;
+}
+
+/**
+ * Pushes p after popping the vertices past the start that would not make a left turn towards it.
+ * The exact orientation keeps nearly collinear points from folding the chain.
+ */
+fn push_turning_left<T: Scalar>(hull: &mut Vec<Vector<T>>, start: usize, p: &Vector<T>) {
+    while hull.len() >= start + 2
+        && Vector::orientation(&hull[hull.len() - 2], &hull[hull.len() - 1], p) != Orientation::Counterclockwise
+    {
+        hull.pop();
+    }
+    hull.push(*p);
+}
+
+impl<T: Scalar> Polygon<T> {
+    /**
+     * Convex hull of the points as a polygon, see convex_hull.
+     * Fails with TooManyVertices if the hull has more than MAX_POLYGON_VERTICES vertices.
+     */
+    pub fn hull(points: &[Vector<T>]) -> Result<Polygon<T>, PolygonError> {
+        return Polygon::new(&convex_hull(points));
+    }
+
+    /**
+     * Convex polygon containing all the points, with at most MAX_POLYGON_VERTICES vertices:
+     * the convex hull, grown to fit the cap (see grow_to_fit) when it has more vertices.
+     * Only an exact hull for hulls that already fit.
+     */
+    pub fn enclosing(points: &[Vector<T>]) -> Result<Polygon<T>, PolygonError> {
+        let mut hull = convex_hull(points);
+        grow_to_fit(&mut hull)?;
+        return Polygon::new(&hull);
+    }
+}
+
+/**
+ * Drops hull edges until at most MAX_POLYGON_VERTICES vertices are left. Each step removes
+ * the edge whose neighbours, extended until they meet, add the least area outside it.
+ * Convex hulls with more than four vertices always have such an edge: the turns at its
+ * two ends add up to less than half a turn. Rounding can still hide it when the edges
+ * are nearly parallel everywhere, that fails with TooManyVertices.
+ */
+fn grow_to_fit<T: Scalar>(hull: &mut Vec<Vector<T>>) -> Result<(), PolygonError> {
+    while hull.len() > MAX_POLYGON_VERTICES {
+        let n = hull.len();
+        let mut best: Option<(T, usize, Vector<T>)> = None;
+        for i in 0..n {
+            let (a, b, c, d) = (hull[(i + n - 1) % n], hull[i], hull[(i + 1) % n], hull[(i + 2) % n]);
+            let (u, w) = (b - a, d - c);
+            // the extended edges only meet past b and c if they turn left less than half a turn
+            let turn = u.perp_dot(&w);
+            if turn <= T::ZERO {
+                continue;
+            }
+            // b + u * k is on the line through c and d
+            let k = (c - b).perp_dot(&w) / turn;
+            let added = u.perp_dot(&(c - b)) * k;
+            let better = match best {
+                Some((area, _, _)) => added < area,
+                None => true,
+            };
+            if better {
+                best = Some((added, i, b + u * k));
+            }
+        }
+        let Some((_, i, p)) = best else {
+            return Err(PolygonError::TooManyVertices);
+        };
+        hull[i] = p;
+        hull.remove((i + 1) % n);
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::Frame;
+
+    /**
+     * Deterministic point cloud, coordinates on a grid of quarters so collinear triples are common
+     */
+    fn cloud(seed: u64, count: usize) -> Vec<Vector> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            return ((state >> 33) % 41) as f32 / 4. - 5.;
+        };
+        return (0..count).map(|_| Vector(next(), next())).collect();
+    }
+
+    fn area(hull: &[Vector]) -> f32 {
+        return (0..hull.len()).map(|i| hull[i].perp_dot(&hull[(i + 1) % hull.len()])).sum::<f32>() / 2.;
+    }
+
+    fn strictly_convex(hull: &[Vector]) -> bool {
+        return (0..hull.len()).all(|i| {
+            let (a, b, c) = (hull[i], hull[(i + 1) % hull.len()], hull[(i + 2) % hull.len()]);
+            return Vector::orientation(&a, &b, &c) == Orientation::Counterclockwise;
+        });
+    }
+
+    #[test]
+    fn square_with_inner_and_edge_points() {
+        let points = [
+            Vector(1., 1.),
+            Vector(0., 0.),
+            Vector(2., 2.),
+            Vector(0., 2.),
+            Vector(1., 0.),
+            Vector(2., 0.),
+            Vector(2., 0.),
+            Vector(0., 1.),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![Vector(0., 0.), Vector(2., 0.), Vector(2., 2.), Vector(0., 2.)]
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        assert_eq!(convex_hull::<f32>(&[]), vec![]);
+        assert_eq!(convex_hull(&[Vector(1., 1.), Vector(1., 1.)]), vec![Vector(1., 1.)]);
+        assert_eq!(
+            convex_hull(&[Vector(2., 2.), Vector(0., 0.), Vector(1., 1.), Vector(3., 3.), Vector(0., 0.)]),
+            vec![Vector(0., 0.), Vector(3., 3.)]
+        );
+        assert_eq!(Polygon::hull(&[Vector(0., 0.), Vector(1., 1.), Vector(2., 2.)]), Err(PolygonError::TooFewVertices));
+    }
+
+    #[test]
+    fn polygon() {
+        let points = [Vector(0., 0.), Vector(0., 3.), Vector(1., 1.), Vector(4., 0.)];
+        let polygon = Polygon::hull(&points).unwrap();
+
+        assert_eq!(polygon.vertices(), &[Vector(0., 0.), Vector(4., 0.), Vector(0., 3.)]);
+        assert_eq!(polygon.area(), 6.);
+    }
+
+    #[test]
+    fn grown_octagon() {
+        // regular 16-gon on the unit circle, half of the edges are dropped
+        let points: Vec<Vector> = (0..16)
+            .map(|i| {
+                let angle = i as f32 * core::f32::consts::PI / 8.;
+                return Vector(angle.cos(), angle.sin());
+            })
+            .collect();
+        assert_eq!(Polygon::hull(&points), Err(PolygonError::TooManyVertices));
+        let polygon = Polygon::enclosing(&points).unwrap();
+
+        assert_eq!(polygon.vertices().len(), MAX_POLYGON_VERTICES);
+        // a bit more than the 16-gon, well under the square around the circle
+        assert!(polygon.area() > area(&points) && polygon.area() < 3.3);
+        for p in &points {
+            assert!(polygon.signed_distance(p) <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn nothing_to_drop() {
+        // clockwise, every pair of extended edges diverges
+        let mut clockwise: Vec<Vector> = (0..9)
+            .map(|i| {
+                let angle = -(i as f32) * core::f32::consts::TAU / 9.;
+                return Vector(angle.cos(), angle.sin());
+            })
+            .collect();
+        assert_eq!(grow_to_fit(&mut clockwise), Err(PolygonError::TooManyVertices));
+        assert_eq!(clockwise.len(), 9);
+    }
+
+    #[test]
+    fn non_finite_points() {
+        let square = [Vector(0., 0.), Vector(2., 0.), Vector(2., 2.), Vector(0., 2.)];
+        let mut points = square.to_vec();
+        points.insert(1, Vector(f32::NAN, 1.));
+        points.insert(3, Vector(1., f32::INFINITY));
+        points.push(Vector(f32::NEG_INFINITY, f32::NAN));
+
+        assert_eq!(convex_hull(&points), square);
+        assert_eq!(convex_hull(&[Vector(f32::NAN, f32::NAN)]), vec![]);
+    }
+
+    #[test]
+    fn random_polygons() {
+        for seed in 0..200 {
+            let points = cloud(seed, 3 + seed as usize % 60);
+            let hull = convex_hull(&points);
+            let polygon = Polygon::enclosing(&points);
+            if hull.len() < 3 {
+                assert_eq!(polygon, Err(PolygonError::TooFewVertices), "seed {}", seed);
+                assert_eq!(Polygon::hull(&points), Err(PolygonError::TooFewVertices), "seed {}", seed);
+                continue;
+            }
+            let polygon = polygon.unwrap();
+
+            if hull.len() <= MAX_POLYGON_VERTICES {
+                assert_eq!(polygon.vertices(), &hull[..], "seed {}", seed);
+                assert_eq!(Polygon::hull(&points), Ok(polygon), "seed {}", seed);
+            } else {
+                assert_eq!(polygon.vertices().len(), MAX_POLYGON_VERTICES, "seed {}", seed);
+                assert_eq!(Polygon::hull(&points), Err(PolygonError::TooManyVertices), "seed {}", seed);
+            }
+            // the grown polygon still holds every point
+            for p in &points {
+                assert!(polygon.signed_distance(p) <= 1e-4, "seed {}", seed);
+            }
+            assert!(polygon.area() >= area(&hull) - 1e-4, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn random_clouds() {
+        for seed in 0..200 {
+            let points = cloud(seed, 3 + seed as usize % 60);
+            let hull = convex_hull(&points);
+            if hull.len() < 3 {
+                continue;
+            }
+
+            assert!(strictly_convex(&hull), "seed {}", seed);
+            // every point inside or on the hull
+            for p in &points {
+                for i in 0..hull.len() {
+                    let edge = (hull[i], hull[(i + 1) % hull.len()]);
+                    assert_ne!(Vector::orientation(&edge.0, &edge.1, p), Orientation::Clockwise, "seed {}", seed);
+                }
+            }
+            // hull vertices are input points and the hull spans the whole cloud
+            assert!(hull.iter().all(|v| points.contains(v)));
+            assert_eq!(Frame::from_points(&hull), Frame::from_points(&points));
+            // input order does not matter
+            let mut reversed = points.clone();
+            reversed.reverse();
+            assert_eq!(convex_hull(&reversed), hull);
+        }
+    }
+}
@@ -1,5 +1,22 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod capsule;
+pub mod chain;
 pub mod circle;
+pub mod contact;
+pub mod curve;
 pub mod frame;
+pub mod gjk;
+pub mod hull;
+pub mod oriented_rectangle;
+pub mod polygon;
+pub mod ray;
 pub mod rectangle;
+mod sat;
 pub mod segment;
-pub mod shape;
\ No newline at end of file
+pub mod shape;
+pub mod simple_polygon;
+pub mod tile_map;
+pub mod traits;
@@ -0,0 +1,246 @@
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::polar::Angle;
+use vector::transform::Transform2D;
+use vector::vector::Vector;
+
+use crate::frame::Frame;
+use crate::gjk::SupportMap;
+use crate::ray::{convex_raycast, Ray, RayHit};
+use crate::rectangle::Rectangle;
+use crate::sat;
+
+/**
+ * Rectangle rotated around its center, for crates, doors and other things that turn
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrientedRectangle<T = f32> {
+    // The center point of rectangle
+    pub location: Vector<T>,
+    // width and height before the rotation
+    pub dementions: Vector<T>,
+    // counter-clockwise
+    pub rotation: Angle<T>,
+}
+
+impl<T: Scalar> OrientedRectangle<T> {
+    pub fn from_rectangle(r: &Rectangle<T>) -> OrientedRectangle<T> {
+        return OrientedRectangle {
+            location: r.location,
+            dementions: r.dementions,
+            rotation: Angle(T::ZERO),
+        };
+    }
+
+    pub fn area(&self) -> T {
+        return self.dementions.0 * self.dementions.1;
+    }
+
+    /**
+     * Half of the width and half of the height along the rotated axes
+     */
+    pub fn half_axes(&self) -> (Vector<T>, Vector<T>) {
+        let half = self.dementions * T::from_f32(0.5);
+        let (x_axis, y_axis) = self.unit_axes();
+        return (x_axis * half.0, y_axis * half.1);
+    }
+
+    /**
+     * Corners in counter-clockwise order, starting from the rotated bottom left one
+     */
+    pub fn corners(&self) -> [Vector<T>; 4] {
+        let (x, y) = self.half_axes();
+        let c = self.location;
+        return [c - x - y, c + x - y, c + x + y, c - x + y];
+    }
+
+    /**
+     * Tight bounding frame of the rotated corners
+     */
+    pub fn to_frame(&self) -> Frame<T> {
+        let (x, y) = self.half_axes();
+        let extent = Vector(x.0.abs() + y.0.abs(), x.1.abs() + y.1.abs());
+        return Frame {
+            from: self.location - extent,
+            to: self.location + extent,
+        };
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        self.location += *offset;
+    }
+
+    /**
+     * Follows the transformed x axis; like Transform2D::decompose, shear gets lost
+     */
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        let (x, y) = self.half_axes();
+        let x = t.apply_direction(&x);
+        let y = t.apply_direction(&y);
+
+        let two = T::from_f32(2.);
+        self.location = t.apply_point(&self.location);
+        self.dementions = Vector(x.size() * two, y.size() * two);
+        self.rotation = Angle(x.rotation());
+    }
+
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        let mut r = *self;
+        r.transform(t);
+        return r.to_frame();
+    }
+
+    /**
+     * True if the point is strictly inside the rectangle
+     */
+    pub fn contains_point(&self, p: &Vector<T>) -> bool {
+        let local = self.local_point(p);
+        let half = self.dementions * T::from_f32(0.5);
+        return local.0.abs() < half.0 && local.1.abs() < half.1;
+    }
+
+    /**
+     * Point of the rectangle nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        let local = self.local_point(p);
+        let closest = self.local_frame().closest_point(&local);
+        if closest == local {
+            return *p;
+        }
+        let (x_axis, y_axis) = self.unit_axes();
+        return self.location + x_axis * closest.0 + y_axis * closest.1;
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.local_frame().distance_to_point(&self.local_point(p));
+    }
+
+    /**
+     * Distance to the nearest side, negative inside
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return self.local_frame().signed_distance(&self.local_point(p));
+    }
+
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        return convex_raycast(&self.corners(), ray);
+    }
+
+    fn unit_axes(&self) -> (Vector<T>, Vector<T>) {
+        let x_axis = Vector(self.rotation.cos(), self.rotation.sin());
+        return (x_axis, x_axis.perp_left());
+    }
+
+    /**
+     * Point in the rectangle's own coordinates, centered and not rotated
+     */
+    fn local_point(&self, p: &Vector<T>) -> Vector<T> {
+        let d = *p - self.location;
+        let (x_axis, y_axis) = self.unit_axes();
+        return Vector(d.dot(&x_axis), d.dot(&y_axis));
+    }
+
+    fn local_frame(&self) -> Frame<T> {
+        let half = self.dementions * T::from_f32(0.5);
+        return Frame { from: -half, to: half };
+    }
+}
+
+impl<T: Scalar> SupportMap<T> for OrientedRectangle<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        return sat::support(&self.corners(), direction);
+    }
+}
+
+impl<T> ApproxEq for OrientedRectangle<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &OrientedRectangle<T>, tolerance: Tolerance<T>) -> bool {
+        return self.location.approx_eq(&other.location, tolerance)
+            && self.dementions.approx_eq(&other.dementions, tolerance)
+            && self.rotation.approx_eq(&other.rotation, tolerance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+    use vector::assert_approx_eq;
+
+    fn door() -> OrientedRectangle {
+        return OrientedRectangle {
+            location: Vector(1., 1.),
+            dementions: Vector(4., 2.),
+            rotation: Angle(FRAC_PI_2),
+        };
+    }
+
+    #[test]
+    fn corners() {
+        let c = door().corners();
+        let expected = [Vector(2., -1.), Vector(2., 3.), Vector(0., 3.), Vector(0., -1.)];
+        for (a, b) in c.iter().zip(expected.iter()) {
+            assert_approx_eq!(*a, *b, Tolerance::Absolute(1e-6));
+        }
+        assert_eq!(door().area(), 8.);
+    }
+
+    #[test]
+    fn to_frame() {
+        let f = door().to_frame();
+        assert_approx_eq!(f, Frame { from: Vector(0., -1.), to: Vector(2., 3.) }, Tolerance::Absolute(1e-6));
+
+        let diamond = OrientedRectangle {
+            location: Vector(0., 0.),
+            dementions: Vector(2., 2.),
+            rotation: Angle(FRAC_PI_4),
+        };
+        let s = 2f32.sqrt();
+        assert_approx_eq!(diamond.to_frame(), Frame { from: Vector(-s, -s), to: Vector(s, s) });
+
+        let axis_aligned = Rectangle {
+            location: Vector(1., 2.),
+            dementions: Vector(3., 4.),
+        };
+        assert_eq!(OrientedRectangle::from_rectangle(&axis_aligned).to_frame(), axis_aligned.to_frame());
+    }
+
+    #[test]
+    fn contains_point() {
+        let d = door();
+
+        assert!(d.contains_point(&Vector(1.5, 2.5)));
+        assert!(!d.contains_point(&Vector(2.5, 1.)));
+        assert!(!d.contains_point(&Vector(-0.5, 1.)));
+    }
+
+    #[test]
+    fn transform() {
+        let mut d = door();
+        d.transform(&Transform2D::from_trs(Vector(1., 0.), -FRAC_PI_2, Vector(2., 2.)));
+
+        assert_approx_eq!(d.location, Vector(3., -2.), Tolerance::Absolute(1e-6));
+        assert_approx_eq!(d.dementions, Vector(8., 4.));
+        assert_approx_eq!(d.rotation.radians(), 0., Tolerance::Absolute(1e-6));
+
+        d.shift(&Vector(-3., 2.));
+        assert_approx_eq!(d.transformed_frame(&Transform2D::identity()), d.to_frame());
+    }
+
+    #[test]
+    fn distance() {
+        let d = door();
+
+        assert_approx_eq!(d.closest_point(&Vector(4., 1.)), Vector(2., 1.), Tolerance::Absolute(1e-6));
+        assert_approx_eq!(d.closest_point(&Vector(3., 5.)), Vector(2., 3.), Tolerance::Absolute(1e-6));
+        assert_eq!(d.closest_point(&Vector(1.5, 0.)), Vector(1.5, 0.));
+        assert_approx_eq!(d.distance_to_point(&Vector(5., 7.)), 5., Tolerance::Absolute(1e-6));
+        assert_approx_eq!(d.signed_distance(&Vector(1., 1.)), -1., Tolerance::Absolute(1e-6));
+    }
+}
@@ -0,0 +1,391 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
+use vector::vector::{Orientation, Vector};
+
+use crate::frame::Frame;
+use crate::gjk::SupportMap;
+use crate::ray::{convex_raycast, Ray, RayHit};
+use crate::rectangle::Rectangle;
+use crate::sat;
+use crate::segment::Segment;
+
+/**
+ * Maximum number of polygon vertices, part of the API: Polygon::new rejects more.
+ * The vertices are stored inline, so Polygon, and with it Shape and PhysObj, stay Copy
+ * and collision queries never allocate. Eight corners are plenty for the slopes and
+ * angled platforms polygons are meant for; larger outlines are split into several polygons.
+ */
+pub const MAX_POLYGON_VERTICES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolygonError {
+    TooFewVertices,
+    TooManyVertices,
+    // vertices are not in convex position, or some of them coincide or are collinear
+    NotConvex,
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            PolygonError::TooFewVertices => write!(f, "polygon needs at least 3 vertices"),
+            PolygonError::TooManyVertices => {
+                write!(f, "polygon can have at most {} vertices", MAX_POLYGON_VERTICES)
+            }
+            PolygonError::NotConvex => write!(f, "polygon is not strictly convex"),
+        };
+    }
+}
+
+/**
+ * Convex polygon, vertices are kept in counter-clockwise order
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// a plain vertex list on disk, hand-edited level data is checked for convexity on load
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "Vec<Vector<T>>",
+        into = "Vec<Vector<T>>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct Polygon<T = f32> {
+    vertices: [Vector<T>; MAX_POLYGON_VERTICES],
+    count: usize,
+}
+
+impl<T: Scalar> Polygon<T> {
+    /**
+     * Validates the vertices: clockwise input is reversed to counter-clockwise,
+     * every vertex must make a strict left turn and the polygon must wind only once
+     */
+    pub fn new(points: &[Vector<T>]) -> Result<Polygon<T>, PolygonError> {
+        if points.len() < 3 {
+            return Err(PolygonError::TooFewVertices);
+        }
+        if points.len() > MAX_POLYGON_VERTICES {
+            return Err(PolygonError::TooManyVertices);
+        }
+
+        let mut polygon = Polygon {
+            vertices: [Vector::default(); MAX_POLYGON_VERTICES],
+            count: points.len(),
+        };
+        polygon.vertices[..points.len()].copy_from_slice(points);
+
+        if polygon.signed_area() < T::ZERO {
+            polygon.reverse();
+        }
+
+        // every other vertex strictly to the left of every edge, with the exact predicate that
+        // triangulation uses too; this also rejects self-intersecting "stars" where all turns are left
+        let vertices = polygon.vertices();
+        for i in 0..vertices.len() {
+            let (a, b) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+            for (j, p) in vertices.iter().enumerate() {
+                if j != i
+                    && j != (i + 1) % vertices.len()
+                    && Vector::orientation(&a, &b, p) != Orientation::Counterclockwise
+                {
+                    return Err(PolygonError::NotConvex);
+                }
+            }
+        }
+
+        return Ok(polygon);
+    }
+
+    /**
+     * Axis-aligned rectangle as a polygon
+     */
+    pub fn from_rectangle(r: &Rectangle<T>) -> Polygon<T> {
+        let f = r.to_frame();
+        let mut polygon = Polygon {
+            vertices: [Vector::default(); MAX_POLYGON_VERTICES],
+            count: 4,
+        };
+        polygon.vertices[..4].copy_from_slice(&[
+            f.from,
+            Vector(f.to.0, f.from.1),
+            f.to,
+            Vector(f.from.0, f.to.1),
+        ]);
+        return polygon;
+    }
+
+    pub fn vertices(&self) -> &[Vector<T>] {
+        return &self.vertices[..self.count];
+    }
+
+    /**
+     * Edges as (start, end) pairs, counter-clockwise
+     */
+    pub fn edges(&self) -> impl Iterator<Item = (Vector<T>, Vector<T>)> + '_ {
+        let vertices = self.vertices();
+        return (0..vertices.len()).map(move |i| (vertices[i], vertices[(i + 1) % vertices.len()]));
+    }
+
+    pub fn area(&self) -> T {
+        return self.signed_area();
+    }
+
+    /**
+     * Center of mass of the polygon area
+     */
+    pub fn centroid(&self) -> Vector<T> {
+        // vertices are taken relative to the first one to keep the products small
+        let origin = self.vertices[0];
+        let mut area = T::ZERO;
+        let mut center = Vector(T::ZERO, T::ZERO);
+        for (a, b) in self.edges() {
+            let cross = (a - origin).perp_dot(&(b - origin));
+            area += cross;
+            center += (a - origin + (b - origin)) * cross;
+        }
+        return origin + center / (area * T::from_f32(3.));
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        let mut from = self.vertices[0];
+        let mut to = self.vertices[0];
+        for p in self.vertices() {
+            from = Vector(from.0.min(p.0), from.1.min(p.1));
+            to = Vector(to.0.max(p.0), to.1.max(p.1));
+        }
+        return Frame { from, to };
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        for p in self.vertices[..self.count].iter_mut() {
+            *p += *offset;
+        }
+    }
+
+    /**
+     * Transforms every vertex, a mirroring transform keeps the winding counter-clockwise
+     */
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        for p in self.vertices[..self.count].iter_mut() {
+            *p = t.apply_point(p);
+        }
+        if t.determinant() < T::ZERO {
+            self.reverse();
+        }
+    }
+
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        let mut p = *self;
+        p.transform(t);
+        return p.to_frame();
+    }
+
+    /**
+     * True if the point is strictly inside the polygon
+     */
+    pub fn contains_point(&self, p: &Vector<T>) -> bool {
+        return self.edges().all(|(a, b)| (b - a).perp_dot(&(*p - a)) > T::ZERO);
+    }
+
+    /**
+     * Point of the polygon nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        if self.contains_point(p) {
+            return *p;
+        }
+        return self.boundary_point(p);
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.closest_point(p).distance(p);
+    }
+
+    /**
+     * Distance to the nearest edge, negative inside
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        let distance = self.boundary_point(p).distance(p);
+        if self.contains_point(p) {
+            return -distance;
+        }
+        return distance;
+    }
+
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        return convex_raycast(self.vertices(), ray);
+    }
+
+    fn signed_area(&self) -> T {
+        let origin = self.vertices[0];
+        let mut area = T::ZERO;
+        for (a, b) in self.edges() {
+            area += (a - origin).perp_dot(&(b - origin));
+        }
+        return area / T::from_f32(2.);
+    }
+
+    fn boundary_point(&self, p: &Vector<T>) -> Vector<T> {
+        let mut best = self.vertices[0];
+        for (a, b) in self.edges() {
+            let q = Segment { location: a, vector: b - a }.closest_point(p);
+            if q.distance_squared(p) < best.distance_squared(p) {
+                best = q;
+            }
+        }
+        return best;
+    }
+
+    fn reverse(&mut self) {
+        self.vertices[..self.count].reverse();
+    }
+}
+
+impl<T: Scalar> SupportMap<T> for Polygon<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        return sat::support(self.vertices(), direction);
+    }
+}
+
+impl<T> ApproxEq for Polygon<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Polygon<T>, tolerance: Tolerance<T>) -> bool {
+        return self.count == other.count
+            && self
+                .vertices()
+                .iter()
+                .zip(other.vertices())
+                .all(|(a, b)| a.approx_eq(b, tolerance));
+    }
+}
+
+impl<T: Scalar> TryFrom<Vec<Vector<T>>> for Polygon<T> {
+    type Error = PolygonError;
+
+    fn try_from(points: Vec<Vector<T>>) -> Result<Polygon<T>, PolygonError> {
+        return Polygon::new(&points);
+    }
+}
+
+impl<T: Scalar> From<Polygon<T>> for Vec<Vector<T>> {
+    fn from(polygon: Polygon<T>) -> Vec<Vector<T>> {
+        return polygon.vertices().to_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::assert_approx_eq;
+
+    fn triangle() -> Polygon {
+        return Polygon::new(&[Vector(0., 0.), Vector(4., 0.), Vector(0., 3.)]).unwrap();
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(Polygon::<f32>::new(&[Vector(0., 0.), Vector(1., 0.)]), Err(PolygonError::TooFewVertices));
+        assert_eq!(Polygon::<f32>::new(&[Vector(0., 0.); 9]), Err(PolygonError::TooManyVertices));
+
+        // concave "arrow"
+        let arrow = [Vector(0., 0.), Vector(2., 1.), Vector(4., 0.), Vector(2., 4.)];
+        assert_eq!(Polygon::new(&arrow), Err(PolygonError::NotConvex));
+
+        // collinear and duplicated vertices
+        let collinear = [Vector(0., 0.), Vector(1., 0.), Vector(2., 0.), Vector(0., 2.)];
+        assert_eq!(Polygon::new(&collinear), Err(PolygonError::NotConvex));
+        let duplicated = [Vector(0., 0.), Vector(1., 0.), Vector(1., 0.), Vector(0., 2.)];
+        assert_eq!(Polygon::new(&duplicated), Err(PolygonError::NotConvex));
+
+        // pentagram turns left at every vertex but winds twice
+        let star: Vec<Vector> = (0..5)
+            .map(|i| {
+                let a = i as f32 * 4. * core::f32::consts::PI / 5.;
+                Vector(a.cos(), a.sin())
+            })
+            .collect();
+        assert_eq!(Polygon::new(&star), Err(PolygonError::NotConvex));
+    }
+
+    #[test]
+    fn clockwise_is_reversed() {
+        let p = Polygon::new(&[Vector(0., 0.), Vector(0., 3.), Vector(4., 0.)]).unwrap();
+
+        assert_eq!(p.area(), 6.);
+        assert_eq!(p.vertices(), &[Vector(4., 0.), Vector(0., 3.), Vector(0., 0.)]);
+    }
+
+    #[test]
+    fn area_and_centroid() {
+        let t = triangle();
+        assert_eq!(t.area(), 6.);
+        assert_approx_eq!(t.centroid(), Vector(4. / 3., 1.));
+
+        let r = Polygon::from_rectangle(&Rectangle {
+            location: Vector(10., 10.),
+            dementions: Vector(2., 4.),
+        });
+        assert_eq!(r.area(), 8.);
+        assert_eq!(r.centroid(), Vector(10., 10.));
+    }
+
+    #[test]
+    fn frame_and_shift() {
+        let mut t = triangle();
+        t.shift(&Vector(1., 1.));
+
+        assert_eq!(t.to_frame(), Frame { from: Vector(1., 1.), to: Vector(5., 4.) });
+        assert!(t.contains_point(&Vector(2., 2.)));
+        assert!(!t.contains_point(&Vector(5., 4.)));
+    }
+
+    #[test]
+    fn mirror_keeps_winding() {
+        let mut t = triangle();
+        t.transform(&Transform2D::from_scale(Vector(-1., 1.)));
+
+        assert_eq!(t.area(), 6.);
+        assert!(t.contains_point(&Vector(-1., 1.)));
+        assert_eq!(t.transformed_frame(&Transform2D::identity()), t.to_frame());
+    }
+
+    #[test]
+    fn distance() {
+        let t = triangle();
+
+        assert_eq!(t.closest_point(&Vector(2., -1.)), Vector(2., 0.));
+        assert_eq!(t.closest_point(&Vector(1., 1.)), Vector(1., 1.));
+        assert_eq!(t.distance_to_point(&Vector(-3., -4.)), 5.);
+        // the hypotenuse 4x + 3y = 12 is the nearest edge
+        assert_approx_eq!(t.signed_distance(&Vector(4., 3.)), 2.4);
+        assert_approx_eq!(t.signed_distance(&Vector(1., 1.)), -1.);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_validates() {
+        let t = triangle();
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(json, "[[0.0,0.0],[4.0,0.0],[0.0,3.0]]");
+        assert_eq!(serde_json::from_str::<Polygon>(&json).unwrap(), t);
+
+        // clockwise input is reversed like in Polygon::new
+        assert_eq!(
+            serde_json::from_str::<Polygon>("[[0,0],[0,3],[4,0]]").unwrap(),
+            Polygon::new(&[Vector(0., 0.), Vector(0., 3.), Vector(4., 0.)]).unwrap()
+        );
+        let error = serde_json::from_str::<Polygon>("[[0,0],[4,0],[1,1],[0,4]]").unwrap_err();
+        assert!(error.to_string().contains("not strictly convex"));
+        assert!(serde_json::from_str::<Polygon>("[[0,0],[4,0]]").is_err());
+        assert!(serde_json::from_str::<Polygon>(r#"{"vertices":[],"count":9}"#).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::vector::Vector;
+
+/**
+ * Half line from the origin along the direction, for hitscan, line of sight
+ * and ground probes. Distances are measured in world units, whatever the
+ * length of the direction. A ray with zero direction only hits shapes
+ * containing its origin.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray<T = f32> {
+    pub origin: Vector<T>,
+    pub direction: Vector<T>,
+    // hits further away are ignored, None for an infinite ray
+    pub max_distance: Option<T>,
+}
+
+/**
+ * Where a ray enters a shape. A ray starting inside the shape hits it
+ * at distance zero, in its origin, with the normal facing the ray.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayHit<T = f32> {
+    pub distance: T,
+    pub point: Vector<T>,
+    // unit length, pointing out of the surface
+    pub normal: Vector<T>,
+}
+
+impl<T: Scalar> Ray<T> {
+    pub fn new(origin: Vector<T>, direction: Vector<T>) -> Ray<T> {
+        return Ray {
+            origin,
+            direction,
+            max_distance: None,
+        };
+    }
+
+    pub fn with_max_distance(self, max_distance: T) -> Ray<T> {
+        return Ray {
+            max_distance: Some(max_distance),
+            ..self
+        };
+    }
+
+    /**
+     * Point at the given distance from the origin
+     */
+    pub fn point_at(&self, distance: T) -> Vector<T> {
+        let mut d = self.direction;
+        d.normalize();
+        return self.origin + d * distance;
+    }
+
+    /**
+     * Hit at the ray parameter t (in lengths of the direction), None past the max distance
+     */
+    pub(crate) fn hit(&self, t: T, normal: Vector<T>) -> Option<RayHit<T>> {
+        let distance = t * self.direction.size();
+        if self.max_distance.is_some_and(|max| distance > max) {
+            return None;
+        }
+        let mut normal = normal;
+        normal.normalize();
+        return Some(RayHit {
+            distance,
+            point: self.origin + self.direction * t,
+            normal,
+        });
+    }
+
+    /**
+     * Hit for a ray starting inside a shape
+     */
+    pub(crate) fn inside(&self) -> Option<RayHit<T>> {
+        return Some(RayHit {
+            distance: T::ZERO,
+            point: self.origin,
+            normal: -self.facing(),
+        });
+    }
+
+    /**
+     * Unit direction, or up for a zero direction
+     */
+    fn facing(&self) -> Vector<T> {
+        let mut d = self.direction;
+        d.normalize();
+        if d.size() == T::ZERO {
+            return Vector(T::ZERO, T::ONE);
+        }
+        return d;
+    }
+}
+
+/**
+ * Clips the ray against every edge of a convex counter-clockwise polygon (Cyrus–Beck)
+ */
+pub(crate) fn convex_raycast<T: Scalar>(vertices: &[Vector<T>], ray: &Ray<T>) -> Option<RayHit<T>> {
+    let mut enter = T::ZERO;
+    let mut exit: Option<T> = None;
+    let mut normal: Option<Vector<T>> = None;
+
+    for i in 0..vertices.len() {
+        let a = vertices[i];
+        let outward = (vertices[(i + 1) % vertices.len()] - a).perp_right();
+        let distance = outward.dot(&(a - ray.origin));
+        let speed = outward.dot(&ray.direction);
+
+        if speed == T::ZERO {
+            // parallel to the edge, outside of it for good
+            if distance < T::ZERO {
+                return None;
+            }
+        } else if speed < T::ZERO {
+            let t = distance / speed;
+            if t > enter || (normal.is_none() && t == enter) {
+                enter = t;
+                normal = Some(outward);
+            }
+        } else {
+            let t = distance / speed;
+            exit = Some(exit.map_or(t, |e| e.min(t)));
+        }
+    }
+
+    if exit.is_some_and(|e| e < enter) {
+        return None;
+    }
+    return match normal {
+        Some(n) => ray.hit(enter, n),
+        None => ray.inside(),
+    };
+}
+
+impl<T> ApproxEq for RayHit<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &RayHit<T>, tolerance: Tolerance<T>) -> bool {
+        return self.distance.approx_eq(&other.distance, tolerance)
+            && self.point.approx_eq(&other.point, tolerance)
+            && self.normal.approx_eq(&other.normal, tolerance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::assert_approx_eq;
+
+    const SQUARE: [Vector; 4] = [Vector(0., 0.), Vector(2., 0.), Vector(2., 2.), Vector(0., 2.)];
+
+    #[test]
+    fn distance_in_world_units() {
+        let ray = Ray::new(Vector(0., 0.), Vector(0., 4.));
+        let hit = ray.hit(0.5, Vector(0., -3.)).unwrap();
+
+        assert_eq!(hit.distance, 2.);
+        assert_eq!(hit.point, Vector(0., 2.));
+        assert_eq!(hit.normal, Vector(0., -1.));
+        assert_eq!(ray.point_at(3.), Vector(0., 3.));
+        assert_eq!(ray.with_max_distance(1.).hit(0.5, Vector(0., -1.)), None);
+    }
+
+    #[test]
+    fn convex() {
+        let ray = Ray::new(Vector(-1., 1.), Vector(2., 0.));
+        let hit = convex_raycast(&SQUARE, &ray).unwrap();
+        assert_eq!(hit, RayHit { distance: 1., point: Vector(0., 1.), normal: Vector(-1., 0.) });
+
+        // diagonal into the corner region
+        let ray = Ray::new(Vector(3., 3.), Vector(-1., -1.));
+        let hit = convex_raycast(&SQUARE, &ray).unwrap();
+        assert_approx_eq!(hit.point, Vector(2., 2.));
+        assert_approx_eq!(hit.distance, 2f32.sqrt());
+
+        // passes by, points away, parallel outside
+        assert_eq!(convex_raycast(&SQUARE, &Ray::new(Vector(-1., 3.), Vector(1., 0.))), None);
+        assert_eq!(convex_raycast(&SQUARE, &Ray::new(Vector(-1., 1.), Vector(-1., 0.))), None);
+        assert_eq!(convex_raycast(&SQUARE, &Ray::new(Vector(3., -1.), Vector(1., 1.))), None);
+    }
+
+    #[test]
+    fn convex_from_inside() {
+        let ray = Ray::new(Vector(1., 1.), Vector(3., 0.));
+        let hit = convex_raycast(&SQUARE, &ray).unwrap();
+        assert_eq!(hit, RayHit { distance: 0., point: Vector(1., 1.), normal: Vector(-1., 0.) });
+
+        let still = Ray::new(Vector(1., 1.), Vector(0., 0.));
+        assert_eq!(convex_raycast(&SQUARE, &still).unwrap().distance, 0.);
+        assert_eq!(convex_raycast(&SQUARE, &Ray::new(Vector(3., 1.), Vector(0., 0.))), None);
+    }
+}
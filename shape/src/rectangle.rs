@@ -1,44 +1,48 @@
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
 use vector::vector::Vector;
 
 
 use super::frame::Frame;
+use super::gjk::SupportMap;
+use super::ray::{Ray, RayHit};
 
-#[derive(Debug,Clone)]
-pub struct Rectangle {
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rectangle<T = f32> {
     // The center point of rectangle
-    pub location: Vector,
+    pub location: Vector<T>,
     // width and height
-    pub dementions: Vector,
+    pub dementions: Vector<T>,
 }
 
-impl Rectangle {
-    pub fn area(&self) -> f32 {
+impl<T: Scalar> Rectangle<T> {
+    pub fn area(&self) -> T {
         return self.dementions.0 * self.dementions.1;
     }
 
-    pub fn to_frame(&self) -> Frame {
-        let mut dementions = self.dementions.clone();
-        let half = dementions.scale(0.5);
+    pub fn to_frame(&self) -> Frame<T> {
+        let half = self.dementions * T::from_f32(0.5);
         return Frame {
-            from: self.location.clone_sub(half).to_owned(),
-            to: self.location.clone_add(half).to_owned(),
+            from: self.location - half,
+            to: self.location + half,
         };
     }
 
-    pub fn shift(&mut self, offs: &Vector) {
-        self.location.add(offs);
-        return;
+    pub fn shift(&mut self, offs: &Vector<T>) {
+        self.location += *offs;
     }
 
-    pub fn clone_shift(&self, offs: &Vector) -> Rectangle{
-        let mut res = self.clone();
+    pub fn clone_shift(&self, offs: &Vector<T>) -> Rectangle<T> {
+        let mut res = *self;
         res.shift(offs);
         return res;
     }
 
-    pub fn from_frame(f: &Frame) -> Rectangle {
-        let  dementions = f.to.clone_sub(&f.from);
-        let  location = f.from.clone_add(&f.to).scale(0.5).to_owned();
+    pub fn from_frame(f: &Frame<T>) -> Rectangle<T> {
+        let dementions = f.to - f.from;
+        let location = (f.from + f.to) * T::from_f32(0.5);
 
         return Rectangle {
             dementions,
@@ -46,7 +50,57 @@ impl Rectangle {
         };
     }
 
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        return self.to_frame().transform(t);
+    }
+
+    /**
+     * Rectangle stays axis-aligned, so after a rotation
+     * it becomes the bounding box of the rotated rectangle
+     */
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        *self = Rectangle::from_frame(&self.transformed_frame(t));
+    }
+
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return self.to_frame().closest_point(p);
+    }
 
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.to_frame().distance_to_point(p);
+    }
+
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return self.to_frame().signed_distance(p);
+    }
+
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        return self.to_frame().raycast(ray);
+    }
+
+
+}
+
+impl<T: Scalar> SupportMap<T> for Rectangle<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        let f = self.to_frame();
+        return Vector(
+            if direction.0 < T::ZERO { f.from.0 } else { f.to.0 },
+            if direction.1 < T::ZERO { f.from.1 } else { f.to.1 },
+        );
+    }
+}
+
+impl<T> ApproxEq for Rectangle<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Rectangle<T>, tolerance: Tolerance<T>) -> bool {
+        return self.location.approx_eq(&other.location, tolerance)
+            && self.dementions.approx_eq(&other.dementions, tolerance);
+    }
 }
 
 
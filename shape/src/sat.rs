@@ -0,0 +1,384 @@
+/*!
+ * Separating axis tests for convex shapes.
+ *
+ * Shapes are given as the vertices of their convex hull, optionally inflated
+ * by a radius (a circle is one vertex with a radius). Two convex shapes are
+ * disjoint iff their projections on some axis do not overlap, and for
+ * polygons it is enough to try the edge normals of both.
+ */
+
+use vector::scalar::Scalar;
+use vector::vector::Vector;
+
+use crate::polygon::MAX_POLYGON_VERTICES;
+use crate::segment::Segment;
+
+/**
+ * Convex hull vertices inflated by a radius, every shape can be described this way
+ */
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Hull<T> {
+    points: [Vector<T>; MAX_POLYGON_VERTICES],
+    count: usize,
+    pub radius: T,
+}
+
+impl<T: Scalar> Hull<T> {
+    pub fn new(points: &[Vector<T>], radius: T) -> Hull<T> {
+        let mut hull = Hull {
+            points: [Vector::default(); MAX_POLYGON_VERTICES],
+            count: points.len(),
+            radius,
+        };
+        hull.points[..points.len()].copy_from_slice(points);
+        return hull;
+    }
+
+    pub fn points(&self) -> &[Vector<T>] {
+        return &self.points[..self.count];
+    }
+}
+
+/**
+ * Overlap of two convex shapes along the axis of least penetration.
+ * The normal is of unit length and points from the first shape to the second.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Penetration<T> {
+    pub normal: Vector<T>,
+    pub depth: T,
+}
+
+impl<T: Scalar> Penetration<T> {
+    /**
+     * Point halfway between the surfaces, from the deepest point of the second shape.
+     * When a whole edge of the second shape is deepest, its middle is taken.
+     */
+    pub fn contact(&self, b: &[Vector<T>], b_radius: T) -> Vector<T> {
+        let direction = -self.normal;
+        let max = support(b, &direction).dot(&direction);
+
+        let mut sum = Vector(T::ZERO, T::ZERO);
+        let mut count = T::ZERO;
+        for p in b.iter().filter(|p| p.dot(&direction) == max) {
+            sum += *p;
+            count += T::ONE;
+        }
+
+        let deepest = sum / count - self.normal * b_radius;
+        return deepest + self.normal * (self.depth / T::from_f32(2.));
+    }
+
+    /**
+     * Up to two contact points halfway between the surfaces. Two points when an edge
+     * of each shape faces the other (a box resting on the floor), one otherwise.
+     */
+    pub fn manifold(&self, a: &[Vector<T>], a_radius: T, b: &[Vector<T>], b_radius: T) -> ([Vector<T>; 2], usize) {
+        let n = self.normal;
+        let tangent = n.perp_left();
+        let surface_a = support(a, &n).dot(&n) + a_radius;
+
+        let (fa, fa_count) = feature(a, &n);
+        let (fb, fb_count) = feature(b, &-n);
+
+        // deepest points of b, as positions along the tangent of its incident feature
+        let mut points = [fb[0]; 2];
+        let mut count = 1;
+        if fb_count == 2 {
+            let (t0, t1) = (fb[0].dot(&tangent), fb[1].dot(&tangent));
+            let on_b = |t: T| fb[0] + (fb[1] - fb[0]) * ((t - t0) / (t1 - t0));
+            let (b_min, b_max) = (t0.min(t1), t0.max(t1));
+
+            if fa_count == 2 {
+                let (s0, s1) = (fa[0].dot(&tangent), fa[1].dot(&tangent));
+                let from = s0.min(s1).max(b_min);
+                let to = s0.max(s1).min(b_max);
+                points = [on_b(from), on_b(to)];
+                count = if from < to { 2 } else { 1 };
+            } else {
+                points[0] = on_b(fa[0].dot(&tangent).max(b_min).min(b_max));
+            }
+        }
+
+        for p in points[..count].iter_mut() {
+            let surface_b = *p - n * b_radius;
+            let depth = surface_a - surface_b.dot(&n);
+            *p = surface_b + n * (depth / T::from_f32(2.));
+        }
+        return (points, count);
+    }
+}
+
+/**
+ * Vertex furthest along the direction, together with a neighbour if the edge
+ * between them is (almost) perpendicular to the direction
+ */
+fn feature<T: Scalar>(points: &[Vector<T>], direction: &Vector<T>) -> ([Vector<T>; 2], usize) {
+    let n = points.len();
+    let i = (0..n).fold(0, |best, k| {
+        if points[k].dot(direction) > points[best].dot(direction) {
+            k
+        } else {
+            best
+        }
+    });
+    if n == 1 {
+        return ([points[i]; 2], 1);
+    }
+
+    let tolerance = T::from_f32(1e-3);
+    for j in [(i + 1) % n, (i + n - 1) % n] {
+        let edge = points[j] - points[i];
+        if edge.dot(direction).abs() <= tolerance * edge.size() && edge.size() > T::ZERO {
+            return ([points[i], points[j]], 2);
+        }
+    }
+    return ([points[i]; 2], 1);
+}
+
+/**
+ * Vertex furthest along the direction
+ */
+pub(crate) fn support<T: Scalar>(points: &[Vector<T>], direction: &Vector<T>) -> Vector<T> {
+    let mut best = points[0];
+    for p in &points[1..] {
+        if p.dot(direction) > best.dot(direction) {
+            best = *p;
+        }
+    }
+    return best;
+}
+
+/**
+ * Separating axis test of the two inflated convex point sets.
+ * The axes are the edge normals of both sets, plus the directions between
+ * vertices when one of the sets is rounded.
+ * None if the shapes are separated or only touch.
+ */
+pub(crate) fn penetration<T: Scalar>(
+    a: &[Vector<T>],
+    a_radius: T,
+    b: &[Vector<T>],
+    b_radius: T,
+) -> Option<Penetration<T>> {
+    let mut best: Option<Penetration<T>> = None;
+    let mut tested = false;
+
+    let mut test_axis = |axis: Vector<T>| -> bool {
+        let mut normal = axis;
+        normal.normalize();
+        if normal.size() == T::ZERO {
+            return true;
+        }
+        tested = true;
+
+        let (a_min, a_max) = project(a, &normal);
+        let (b_min, b_max) = project(b, &normal);
+        // distance b has to move along the axis, in either direction, to get out of a
+        let forward = a_max + a_radius - (b_min - b_radius);
+        let backward = b_max + b_radius - (a_min - a_radius);
+        let depth = forward.min(backward);
+        if depth <= T::ZERO {
+            return false;
+        }
+
+        // orient the normal from a to b
+        if backward < forward {
+            normal = -normal;
+        }
+        if best.is_none_or(|p| depth < p.depth) {
+            best = Some(Penetration { normal, depth });
+        }
+        return true;
+    };
+
+    for points in [a, b] {
+        if points.len() < 2 {
+            continue;
+        }
+        for i in 0..points.len() {
+            let edge = points[(i + 1) % points.len()] - points[i];
+            if !test_axis(edge.perp_right()) {
+                return None;
+            }
+        }
+    }
+
+    // rounded corners of the Minkowski difference face the directions between vertex pairs
+    if a_radius > T::ZERO || b_radius > T::ZERO || (a.len() == 1 && b.len() == 1) {
+        for p in a {
+            for q in b {
+                if !test_axis(*q - *p) {
+                    return None;
+                }
+            }
+        }
+    }
+
+    // all vertices coincide (like concentric circles), any direction separates them equally well
+    if !tested && a_radius + b_radius > T::ZERO {
+        return Some(Penetration {
+            normal: Vector(T::ZERO, T::ONE),
+            depth: a_radius + b_radius,
+        });
+    }
+
+    return best;
+}
+
+/**
+ * Gap between the surfaces of the two inflated convex point sets, zero if they touch or overlap.
+ * Disjoint convex sets are nearest at a vertex of one of them, so it is enough
+ * to measure every vertex against every edge of the other set.
+ */
+pub(crate) fn distance<T: Scalar>(a: &[Vector<T>], a_radius: T, b: &[Vector<T>], b_radius: T) -> T {
+    if penetration(a, a_radius, b, b_radius).is_some() {
+        return T::ZERO;
+    }
+
+    let mut nearest: Option<T> = None;
+    for (points, others) in [(a, b), (b, a)] {
+        for i in 0..others.len() {
+            let edge = Segment {
+                location: others[i],
+                vector: others[(i + 1) % others.len()] - others[i],
+            };
+            for p in points {
+                let d = edge.distance_to_point(p);
+                nearest = Some(nearest.map_or(d, |n| n.min(d)));
+            }
+        }
+    }
+    return nearest.map_or(T::ZERO, |n| (n - a_radius - b_radius).max(T::ZERO));
+}
+
+fn project<T: Scalar>(points: &[Vector<T>], axis: &Vector<T>) -> (T, T) {
+    let mut min = points[0].dot(axis);
+    let mut max = min;
+    for p in &points[1..] {
+        let d = p.dot(axis);
+        min = min.min(d);
+        max = max.max(d);
+    }
+    return (min, max);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector::assert_approx_eq;
+
+    const SQUARE: [Vector; 4] = [Vector(0., 0.), Vector(2., 0.), Vector(2., 2.), Vector(0., 2.)];
+
+    #[test]
+    fn polygons() {
+        let b = [Vector(1.5, 0.5), Vector(3.5, 0.5), Vector(3.5, 1.5)];
+        let p = penetration(&SQUARE, 0., &b, 0.).unwrap();
+
+        assert_eq!(p.normal, Vector(1., 0.));
+        assert_eq!(p.depth, 0.5);
+        assert_eq!(p.contact(&b, 0.), Vector(1.75, 0.5));
+
+        let far = [Vector(3., 0.), Vector(4., 0.), Vector(4., 1.)];
+        assert_eq!(penetration(&SQUARE, 0., &far, 0.), None);
+
+        // touching is not a collision
+        let touching = [Vector(2., 0.), Vector(4., 0.), Vector(4., 1.)];
+        assert_eq!(penetration(&SQUARE, 0., &touching, 0.), None);
+    }
+
+    #[test]
+    fn rounded() {
+        // circle near the corner, separated along the diagonal only
+        let c = [Vector(2.8, 2.8)];
+        assert_eq!(penetration(&SQUARE, 0., &c, 1.), None);
+
+        let c = [Vector(2.5, 2.5)];
+        let p = penetration(&SQUARE, 0., &c, 1.).unwrap();
+        assert_approx_eq!(p.normal, Vector(0.70710677, 0.70710677));
+        assert_approx_eq!(p.depth, 1. - 0.5 * 2f32.sqrt());
+    }
+
+    #[test]
+    fn contained() {
+        // a segment inside the square has no extent along x, it still has to be pushed out
+        let s = [Vector(0.5, 1.), Vector(0.5, 1.5)];
+        let p = penetration(&SQUARE, 0., &s, 0.).unwrap();
+
+        assert_eq!(p.normal, Vector(-1., 0.));
+        assert_eq!(p.depth, 0.5);
+    }
+
+    #[test]
+    fn edge_contact() {
+        let b = [Vector(1.5, 0.5), Vector(3.5, 0.5), Vector(3.5, 1.5), Vector(1.5, 1.5)];
+        let p = penetration(&SQUARE, 0., &b, 0.).unwrap();
+
+        assert_eq!(p.contact(&b, 0.), Vector(1.75, 1.));
+    }
+
+    #[test]
+    fn concentric() {
+        let c = [Vector(1., 1.)];
+        let p = penetration(&c, 1., &c, 0.5).unwrap();
+
+        assert_eq!(p.depth, 1.5);
+        assert_eq!(penetration(&c, 0., &c, 0.), None);
+    }
+
+    #[test]
+    fn manifold_face_to_face() {
+        // box sinking 0.2 into the top face of the square
+        let b = [Vector(1., 1.8), Vector(3., 1.8), Vector(3., 2.8), Vector(1., 2.8)];
+        let p = penetration(&SQUARE, 0., &b, 0.).unwrap();
+        let (points, count) = p.manifold(&SQUARE, 0., &b, 0.);
+
+        assert_eq!(p.normal, Vector(0., 1.));
+        assert_approx_eq!(p.depth, 0.2);
+        assert_eq!(count, 2);
+        // ordered along the tangent, which points to the left of the normal
+        assert_approx_eq!(points[0], Vector(2., 1.9));
+        assert_approx_eq!(points[1], Vector(1., 1.9));
+    }
+
+    #[test]
+    fn manifold_corner() {
+        // triangle poking into the top face with its corner
+        let b = [Vector(1., 1.8), Vector(2., 3.), Vector(0., 3.)];
+        let p = penetration(&SQUARE, 0., &b, 0.).unwrap();
+        let (points, count) = p.manifold(&SQUARE, 0., &b, 0.);
+
+        assert_eq!(count, 1);
+        assert_approx_eq!(points[0], Vector(1., 1.9));
+
+        // the same with a flat edge of the square inside a rounded shape
+        let capsule = [Vector(-1., 2.5), Vector(3., 2.5)];
+        let p = penetration(&SQUARE, 0., &capsule, 1.).unwrap();
+        let (points, count) = p.manifold(&SQUARE, 0., &capsule, 1.);
+
+        assert_eq!(count, 2);
+        assert_eq!(points[..2], [Vector(2., 1.75), Vector(0., 1.75)]);
+    }
+
+    #[test]
+    fn gap() {
+        let b = [Vector(3., 1.), Vector(4., 0.), Vector(4., 2.)];
+        assert_eq!(distance(&SQUARE, 0., &b, 0.), 1.);
+        assert_eq!(distance(&SQUARE, 0., &b, 0.5), 0.5);
+        assert_eq!(distance(&SQUARE, 0.5, &b, 0.5), 0.);
+
+        // corner to corner
+        let c = [Vector(5., 6.)];
+        assert_eq!(distance(&SQUARE, 0., &c, 0.), 5.);
+        assert_eq!(distance(&c, 1., &SQUARE, 0.), 4.);
+
+        let overlapping = [Vector(1., 1.), Vector(3., 1.), Vector(3., 3.)];
+        assert_eq!(distance(&SQUARE, 0., &overlapping, 0.), 0.);
+    }
+
+    #[test]
+    fn support_point() {
+        assert_eq!(support(&SQUARE, &Vector(1., 1.)), Vector(2., 2.));
+        assert_eq!(support(&SQUARE, &Vector(-1., 0.1)), Vector(0., 2.));
+    }
+}
@@ -1,64 +1,218 @@
-use vector::vector::Vector;
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
+use vector::vector::{Orientation, Vector};
 
 use crate::frame::Frame;
+use crate::gjk::SupportMap;
+use crate::ray::{Ray, RayHit};
 
 /**
  * Segment described as start point and vector
  */
-#[derive(Debug,PartialEq)]
-pub struct Segment {
-    pub location: Vector,
-    pub vector: Vector,
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<T = f32> {
+    pub location: Vector<T>,
+    pub vector: Vector<T>,
 }
 
-impl Segment {
+impl<T: Scalar> Segment<T> {
     /**
      * Get vector end point
      */
-    pub fn end(&self) -> Vector {
-        return self.location.clone().add(&self.vector).to_owned();
+    pub fn end(&self) -> Vector<T> {
+        return self.location + self.vector;
     }
 
-    pub fn to_frame(&self) -> Frame {
+    pub fn to_frame(&self) -> Frame<T> {
         let end = self.end();
 
         return Frame {
             from: Vector(
-                f32::min(self.location.0, end.0),
-                f32::min(self.location.1, end.1),
+                T::min(self.location.0, end.0),
+                T::min(self.location.1, end.1),
             ),
             to: Vector(
-                f32::max(self.location.0, end.0),
-                f32::max(self.location.1, end.1),
+                T::max(self.location.0, end.0),
+                T::max(self.location.1, end.1),
             ),
         };
     }
 
-    pub fn scale(&mut self, s: f32) -> &mut Self {
+    pub fn scale(&mut self, s: T) -> &mut Self {
         self.vector.scale(s);
         return self;
     }
 
-    pub fn shift(&mut self, s: &Vector)-> &mut Self  {
-        self.location.add(s);
+    pub fn shift(&mut self, s: &Vector<T>)-> &mut Self  {
+        self.location += *s;
         return self;
     }
 
-    pub fn project_point(&self, p: &Vector) -> Vector {
-        let a = self.location.clone();
-        let b = self.end();
+    pub fn transform(&mut self, t: &Transform2D<T>) -> &mut Self {
+        self.location = t.apply_point(&self.location);
+        self.vector = t.apply_direction(&self.vector);
+        return self;
+    }
 
-        let ab = b.clone_sub(&a);
-        let ap = p.clone_sub(&a);
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        let mut s = *self;
+        return s.transform(t).to_frame();
+    }
 
-        let ab_dot_ab = ab.dot(&ab);
+    pub fn project_point(&self, p: &Vector<T>) -> Vector<T> {
+        // project_onto of a zero vector is zero, so a segment that is a point projects onto itself
+        let ap = *p - self.location;
+        return self.location + ap.project_onto(&self.vector);
+    }
 
-        if ab_dot_ab == 0.0 {
-            return a.clone(); // line is a point
+    /**
+     * Point of the segment nearest to p, unlike project_point it stays between the ends
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        let length_squared = self.vector.size_squared();
+        if length_squared == T::ZERO {
+            return self.location;
         }
+        let t = ((*p - self.location).dot(&self.vector) / length_squared).max(T::ZERO).min(T::ONE);
+        return self.location + self.vector * t;
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.closest_point(p).distance(p);
+    }
 
-        let t = ap.dot(&ab) / ab_dot_ab;
-        return self.location.clone_add(&ab.clone_scale(t));
+    /**
+     * A segment has no inside, so this is the same as distance_to_point
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return self.distance_to_point(p);
+    }
+
+    /**
+     * Common point of the two closed segments. Crossing segments give the crossing point,
+     * overlapping collinear segments the middle of the overlap.
+     * Which side of each other the ends are is decided exactly, so touching counts.
+     */
+    pub fn intersection(&self, other: &Segment<T>) -> Option<Vector<T>> {
+        let (a, b) = (self.location, self.end());
+        let (c, d) = (other.location, other.end());
+
+        let o1 = Vector::orientation(&a, &b, &c);
+        let o2 = Vector::orientation(&a, &b, &d);
+        let o3 = Vector::orientation(&c, &d, &a);
+        let o4 = Vector::orientation(&c, &d, &b);
+
+        let collinear = Orientation::Collinear;
+        if o1 != o2 && o3 != o4 && o1 != collinear && o2 != collinear && o3 != collinear && o4 != collinear {
+            let t = (c - a).perp_dot(&other.vector) / self.vector.perp_dot(&other.vector);
+            return Some(a + self.vector * t);
+        }
+
+        if o1 == collinear && o2 == collinear {
+            // ends of the overlap are ends of the segments lying on the other one
+            let direction = if self.vector == Vector(T::ZERO, T::ZERO) {
+                other.vector
+            } else {
+                self.vector
+            };
+            let mut overlap: Option<(Vector<T>, Vector<T>)> = None;
+            let candidates = [(c, self), (d, self), (a, other), (b, other)];
+            for (p, s) in candidates {
+                if !s.frame_contains(&p) {
+                    continue;
+                }
+                overlap = match overlap {
+                    None => Some((p, p)),
+                    Some((min, max)) if p.dot(&direction) < min.dot(&direction) => Some((p, max)),
+                    Some((min, max)) if p.dot(&direction) > max.dot(&direction) => Some((min, p)),
+                    o => o,
+                };
+            }
+            return overlap.map(|(min, max)| (min + max) / T::from_f32(2.));
+        }
+
+        if o1 == collinear && self.frame_contains(&c) {
+            return Some(c);
+        }
+        if o2 == collinear && self.frame_contains(&d) {
+            return Some(d);
+        }
+        if o3 == collinear && other.frame_contains(&a) {
+            return Some(a);
+        }
+        if o4 == collinear && other.frame_contains(&b) {
+            return Some(b);
+        }
+        return None;
+    }
+
+    /**
+     * The normal of the hit faces the ray, a ray along the segment hits its nearer end
+     */
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        let d = ray.direction;
+        let to_start = self.location - ray.origin;
+        let zero = Vector(T::ZERO, T::ZERO);
+        if d == zero {
+            let origin = Segment { location: ray.origin, vector: zero };
+            return self.intersection(&origin).and_then(|_| ray.inside());
+        }
+
+        let denominator = d.perp_dot(&self.vector);
+        if denominator == T::ZERO {
+            if to_start.perp_dot(&d) != T::ZERO {
+                return None;
+            }
+            let length_squared = d.size_squared();
+            let t_start = to_start.dot(&d) / length_squared;
+            let t_end = (to_start + self.vector).dot(&d) / length_squared;
+            if t_start.max(t_end) < T::ZERO {
+                return None;
+            }
+            return ray.hit(t_start.min(t_end).max(T::ZERO), -d);
+        }
+
+        let t = to_start.perp_dot(&self.vector) / denominator;
+        let u = to_start.perp_dot(&d) / denominator;
+        if t < T::ZERO || u < T::ZERO || u > T::ONE {
+            return None;
+        }
+        let mut normal = self.vector.perp_left();
+        if normal.dot(&d) > T::ZERO {
+            normal = -normal;
+        }
+        return ray.hit(t, normal);
+    }
+
+    /**
+     * For a point on the segment's line, true if it is between the ends
+     */
+    fn frame_contains(&self, p: &Vector<T>) -> bool {
+        let f = self.to_frame();
+        return p.0 >= f.from.0 && p.0 <= f.to.0 && p.1 >= f.from.1 && p.1 <= f.to.1;
+    }
+}
+
+impl<T: Scalar> SupportMap<T> for Segment<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        if self.vector.dot(direction) > T::ZERO {
+            return self.end();
+        }
+        return self.location;
+    }
+}
+
+impl<T> ApproxEq for Segment<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Segment<T>, tolerance: Tolerance<T>) -> bool {
+        return self.location.approx_eq(&other.location, tolerance)
+            && self.vector.approx_eq(&other.vector, tolerance);
     }
 }
 
@@ -141,4 +295,90 @@ mod tests {
         
     }
 
+    #[test]
+    fn closest_point() {
+        let s = Segment {
+            location: Vector(0., 0.),
+            vector: Vector(0., 2.),
+        };
+
+        assert_eq!(s.closest_point(&Vector(1., 1.)), Vector(0., 1.));
+        assert_eq!(s.closest_point(&Vector(1., 3.)), Vector(0., 2.));
+        assert_eq!(s.closest_point(&Vector(-1., -3.)), Vector(0., 0.));
+
+        let point = Segment {
+            location: Vector(1., 1.),
+            vector: Vector(0., 0.),
+        };
+        assert_eq!(point.closest_point(&Vector(5., 5.)), Vector(1., 1.));
+    }
+
+    #[test]
+    fn intersection() {
+        let s = |x: f32, y: f32, dx: f32, dy: f32| Segment {
+            location: Vector(x, y),
+            vector: Vector(dx, dy),
+        };
+
+        // crossing
+        assert_eq!(s(-1., -1., 2., 2.).intersection(&s(-1., 1., 2., -2.)), Some(Vector(0., 0.)));
+        // end touching the middle
+        assert_eq!(s(0., 0., 0., 1.).intersection(&s(-1., 0., 2., 0.)), Some(Vector(0., 0.)));
+        assert_eq!(s(-1., 0., 2., 0.).intersection(&s(0., 1., 0., -1.)), Some(Vector(0., 0.)));
+        // collinear overlap
+        assert_eq!(s(0., 0., 2., 0.).intersection(&s(1., 0., 2., 0.)), Some(Vector(1.5, 0.)));
+        assert_eq!(s(0., 0., 4., 0.).intersection(&s(3., 0., -2., 0.)), Some(Vector(2., 0.)));
+        // collinear, apart
+        assert_eq!(s(0., 0., 1., 0.).intersection(&s(2., 0., 1., 0.)), None);
+        // parallel
+        assert_eq!(s(0., 0., 2., 0.).intersection(&s(0., 1., 2., 0.)), None);
+        // the frames overlap, the segments do not
+        assert_eq!(s(0., 0., 2., 2.).intersection(&s(1.5, 0., 0.5, 1.)), None);
+    }
+
+    #[test]
+    fn raycast() {
+        let wall = Segment {
+            location: Vector(2., -1.),
+            vector: Vector(0., 2.),
+        };
+
+        let hit = wall.raycast(&Ray::new(Vector(0., 0.), Vector(1., 0.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 2., point: Vector(2., 0.), normal: Vector(-1., 0.) });
+
+        // the normal faces the ray from either side
+        let hit = wall.raycast(&Ray::new(Vector(4., 0.), Vector(-1., 0.))).unwrap();
+        assert_eq!(hit.normal, Vector(1., 0.));
+
+        assert_eq!(wall.raycast(&Ray::new(Vector(0., 0.), Vector(1., 1.))), None);
+        assert_eq!(wall.raycast(&Ray::new(Vector(0., 0.), Vector(1., 0.)).with_max_distance(1.)), None);
+    }
+
+    #[test]
+    fn raycast_along() {
+        let floor = Segment {
+            location: Vector(2., 0.),
+            vector: Vector(2., 0.),
+        };
+
+        let hit = floor.raycast(&Ray::new(Vector(0., 0.), Vector(3., 0.))).unwrap();
+        assert_eq!(hit, RayHit { distance: 2., point: Vector(2., 0.), normal: Vector(-1., 0.) });
+
+        assert_eq!(floor.raycast(&Ray::new(Vector(3., 0.), Vector(1., 0.))).unwrap().distance, 0.);
+        assert_eq!(floor.raycast(&Ray::new(Vector(3., 0.), Vector(0., 0.))).unwrap().distance, 0.);
+        assert_eq!(floor.raycast(&Ray::new(Vector(5., 0.), Vector(1., 0.))), None);
+        assert_eq!(floor.raycast(&Ray::new(Vector(0., 1.), Vector(1., 0.))), None);
+    }
+
+    #[test]
+    fn distance() {
+        let s = Segment {
+            location: Vector(0., 0.),
+            vector: Vector(4., 0.),
+        };
+
+        assert_eq!(s.distance_to_point(&Vector(2., 3.)), 3.);
+        assert_eq!(s.distance_to_point(&Vector(7., 4.)), 5.);
+        assert_eq!(s.signed_distance(&Vector(2., 0.)), 0.);
+    }
 }
@@ -1,91 +1,373 @@
 use crate::frame::Frame;
+use super::capsule::Capsule;
 use super::circle::Circle;
+use super::contact::Contact;
+use super::gjk::SupportMap;
+use super::oriented_rectangle::OrientedRectangle;
+use super::polygon::Polygon;
+use super::ray::{Ray, RayHit};
 use super::rectangle::Rectangle;
+use super::sat;
 use super::segment::Segment;
+use vector::scalar::Scalar;
+use vector::approx::{ApproxEq, Tolerance};
+use vector::transform::Transform2D;
 use vector::vector::Vector;
 
-#[derive(Debug)]
-pub enum Shape {
-    Dot(Vector),
-    Rectangle(Rectangle),
-    Circle(Circle),
-    Segment(Segment),
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        rename_all = "snake_case",
+        // polygons are validated on load, see Polygon
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub enum Shape<T = f32> {
+    Dot(Vector<T>),
+    Rectangle(Rectangle<T>),
+    Circle(Circle<T>),
+    Segment(Segment<T>),
+    Polygon(Polygon<T>),
+    Capsule(Capsule<T>),
+    OrientedRectangle(OrientedRectangle<T>),
 }
 
-impl Shape {
+impl<T: Scalar> Shape<T> {
 
-    pub fn shift(&mut self, offset: &Vector) {
+    pub fn shift(&mut self, offset: &Vector<T>) {
         match self {
             Shape::Dot(v) => {
-                v.add(offset);
+                *v += *offset;
             }
             Shape::Rectangle(r) => {
-                r.location.add(offset);
+                r.location += *offset;
             }
             Shape::Circle(c) => {
-                c.location.add(offset);
+                c.location += *offset;
             }
             Shape::Segment(s) => {
-                s.location.add(offset);
+                s.location += *offset;
+            }
+            Shape::Polygon(p) => {
+                p.shift(offset);
+            }
+            Shape::Capsule(c) => {
+                c.shift(offset);
+            }
+            Shape::OrientedRectangle(r) => {
+                r.shift(offset);
             }
         }
     }
 
-    pub fn to_frame(&self) -> Frame {
+    pub fn to_frame(&self) -> Frame<T> {
         match self {
-            Shape::Dot(p) => Frame {
-                from: p.clone(),
-                to: p.clone(),
-            },
+            Shape::Dot(p) => Frame { from: *p, to: *p },
             Shape::Rectangle(r) => r.to_frame(),
             Shape::Circle(c) => c.to_frame(),
             Shape::Segment(s) => s.to_frame(),
+            Shape::Polygon(p) => p.to_frame(),
+            Shape::Capsule(c) => c.to_frame(),
+            Shape::OrientedRectangle(r) => r.to_frame(),
+        }
+    }
+
+    pub fn transform(&mut self, t: &Transform2D<T>) {
+        match self {
+            Shape::Dot(v) => {
+                *v = t.apply_point(v);
+            }
+            Shape::Rectangle(r) => {
+                // a rectangle only stays axis-aligned without rotation or shear
+                if t.x_axis.1 == T::ZERO && t.y_axis.0 == T::ZERO {
+                    r.transform(t);
+                } else {
+                    let mut rotated = OrientedRectangle::from_rectangle(r);
+                    rotated.transform(t);
+                    *self = Shape::OrientedRectangle(rotated);
+                }
+            }
+            Shape::Circle(c) => {
+                c.transform(t);
+            }
+            Shape::Segment(s) => {
+                s.transform(t);
+            }
+            Shape::Polygon(p) => {
+                p.transform(t);
+            }
+            Shape::Capsule(c) => {
+                c.transform(t);
+            }
+            Shape::OrientedRectangle(r) => {
+                r.transform(t);
+            }
         }
     }
 
-    pub fn collision_with(&self, shape: &Shape) -> Option<Vector> {
+    /**
+     * Bounding frame of the shape after the transform, tight also for rotated shapes
+     */
+    pub fn transformed_frame(&self, t: &Transform2D<T>) -> Frame<T> {
+        match self {
+            Shape::Dot(p) => {
+                let p = t.apply_point(p);
+                Frame { from: p, to: p }
+            }
+            Shape::Rectangle(r) => r.transformed_frame(t),
+            Shape::Circle(c) => c.transformed_frame(t),
+            Shape::Segment(s) => s.transformed_frame(t),
+            Shape::Polygon(p) => p.transformed_frame(t),
+            Shape::Capsule(c) => c.transformed_frame(t),
+            Shape::OrientedRectangle(r) => r.transformed_frame(t),
+        }
+    }
+
+    /**
+     * Dots and segments have no area
+     */
+    pub fn area(&self) -> T {
+        return match self {
+            Shape::Dot(_) | Shape::Segment(_) => T::ZERO,
+            Shape::Rectangle(r) => r.area(),
+            Shape::Circle(c) => c.area(),
+            Shape::Polygon(p) => p.area(),
+            Shape::Capsule(c) => c.area(),
+            Shape::OrientedRectangle(r) => r.area(),
+        };
+    }
+
+    /**
+     * Point where the shapes collide, None if they do not.
+     * Solid shapes collide when their interiors overlap, a dot or a segment
+     * collides with another segment or a dot when they have a common point.
+     * The result does not depend on the order of the shapes.
+     */
+    pub fn collision_with(&self, shape: &Shape<T>) -> Option<Vector<T>> {
+        // every pair is listed (no catch-all), so a new variant does not compile until it is handled here
         return match (self, shape) {
             (Shape::Dot(a), Shape::Dot(b)) => {
                 if a.eq(b) {
-                    Option::Some(a.clone())
+                    Option::Some(*a)
                 } else {
                     Option::None
                 }
             }
-            (Shape::Dot(a), Shape::Circle(c)) => Shape::dot_in_circle(c, a),
-            (Shape::Circle(c), Shape::Dot(a)) => Shape::dot_in_circle(c, a),
-            (Shape::Circle(c1), Shape::Circle(c2)) => Shape::circles_collision(c1, c2),
+            (Shape::Dot(a), Shape::Rectangle(r)) | (Shape::Rectangle(r), Shape::Dot(a)) => {
+                Shape::dot_in(Polygon::from_rectangle(r).contains_point(a), a)
+            }
+            (Shape::Dot(a), Shape::Circle(c)) | (Shape::Circle(c), Shape::Dot(a)) => Shape::dot_in_circle(c, a),
+            (Shape::Dot(a), Shape::Segment(s)) | (Shape::Segment(s), Shape::Dot(a)) => {
+                s.intersection(&Segment { location: *a, vector: Vector(T::ZERO, T::ZERO) })
+            }
+            (Shape::Dot(a), Shape::Polygon(p)) | (Shape::Polygon(p), Shape::Dot(a)) => {
+                Shape::dot_in(p.contains_point(a), a)
+            }
+            (Shape::Dot(a), Shape::Capsule(c)) | (Shape::Capsule(c), Shape::Dot(a)) => {
+                Shape::dot_in(c.contains_point(a), a)
+            }
+            (Shape::Dot(a), Shape::OrientedRectangle(r)) | (Shape::OrientedRectangle(r), Shape::Dot(a)) => {
+                Shape::dot_in(r.contains_point(a), a)
+            }
+
             (Shape::Rectangle(r1), Shape::Rectangle(r2)) => Shape::reactangles_collision(r1, r2),
+            (Shape::Rectangle(r), Shape::Circle(c)) | (Shape::Circle(c), Shape::Rectangle(r)) => {
+                Shape::convex_collision(Polygon::from_rectangle(r).vertices(), T::ZERO, &[c.location], c.radius)
+            }
+            (Shape::Rectangle(r), Shape::Segment(s)) | (Shape::Segment(s), Shape::Rectangle(r)) => {
+                Shape::convex_collision(Polygon::from_rectangle(r).vertices(), T::ZERO, &[s.location, s.end()], T::ZERO)
+            }
+            (Shape::Rectangle(r), Shape::Polygon(p)) | (Shape::Polygon(p), Shape::Rectangle(r)) => {
+                Shape::convex_collision(p.vertices(), T::ZERO, Polygon::from_rectangle(r).vertices(), T::ZERO)
+            }
+            (Shape::Rectangle(r), Shape::Capsule(c)) | (Shape::Capsule(c), Shape::Rectangle(r)) => {
+                Shape::convex_collision(Polygon::from_rectangle(r).vertices(), T::ZERO, &c.ends(), c.radius)
+            }
+            (Shape::Rectangle(r), Shape::OrientedRectangle(o)) | (Shape::OrientedRectangle(o), Shape::Rectangle(r)) => {
+                Shape::convex_collision(&o.corners(), T::ZERO, Polygon::from_rectangle(r).vertices(), T::ZERO)
+            }
 
-            // not implemented
-            (_, _) => Option::None,
+            (Shape::Circle(c1), Shape::Circle(c2)) => Shape::circles_collision(c1, c2),
+            (Shape::Circle(c), Shape::Segment(s)) | (Shape::Segment(s), Shape::Circle(c)) => {
+                Shape::convex_collision(&[s.location, s.end()], T::ZERO, &[c.location], c.radius)
+            }
+            (Shape::Circle(c), Shape::Polygon(p)) | (Shape::Polygon(p), Shape::Circle(c)) => {
+                Shape::convex_collision(p.vertices(), T::ZERO, &[c.location], c.radius)
+            }
+            (Shape::Circle(o), Shape::Capsule(c)) | (Shape::Capsule(c), Shape::Circle(o)) => {
+                Shape::convex_collision(&c.ends(), c.radius, &[o.location], o.radius)
+            }
+            (Shape::Circle(c), Shape::OrientedRectangle(r)) | (Shape::OrientedRectangle(r), Shape::Circle(c)) => {
+                Shape::convex_collision(&r.corners(), T::ZERO, &[c.location], c.radius)
+            }
+
+            (Shape::Segment(s1), Shape::Segment(s2)) => s1.intersection(s2),
+            (Shape::Segment(s), Shape::Polygon(p)) | (Shape::Polygon(p), Shape::Segment(s)) => {
+                Shape::convex_collision(p.vertices(), T::ZERO, &[s.location, s.end()], T::ZERO)
+            }
+            (Shape::Segment(s), Shape::Capsule(c)) | (Shape::Capsule(c), Shape::Segment(s)) => {
+                Shape::convex_collision(&c.ends(), c.radius, &[s.location, s.end()], T::ZERO)
+            }
+            (Shape::Segment(s), Shape::OrientedRectangle(r)) | (Shape::OrientedRectangle(r), Shape::Segment(s)) => {
+                Shape::convex_collision(&r.corners(), T::ZERO, &[s.location, s.end()], T::ZERO)
+            }
+
+            (Shape::Polygon(p1), Shape::Polygon(p2)) => {
+                Shape::convex_collision(p1.vertices(), T::ZERO, p2.vertices(), T::ZERO)
+            }
+            (Shape::Polygon(p), Shape::Capsule(c)) | (Shape::Capsule(c), Shape::Polygon(p)) => {
+                Shape::convex_collision(p.vertices(), T::ZERO, &c.ends(), c.radius)
+            }
+            (Shape::Polygon(p), Shape::OrientedRectangle(r)) | (Shape::OrientedRectangle(r), Shape::Polygon(p)) => {
+                Shape::convex_collision(p.vertices(), T::ZERO, &r.corners(), T::ZERO)
+            }
+
+            (Shape::Capsule(c1), Shape::Capsule(c2)) => {
+                Shape::convex_collision(&c1.ends(), c1.radius, &c2.ends(), c2.radius)
+            }
+            (Shape::Capsule(c), Shape::OrientedRectangle(r)) | (Shape::OrientedRectangle(r), Shape::Capsule(c)) => {
+                Shape::convex_collision(&r.corners(), T::ZERO, &c.ends(), c.radius)
+            }
+
+            (Shape::OrientedRectangle(r1), Shape::OrientedRectangle(r2)) => {
+                Shape::convex_collision(&r1.corners(), T::ZERO, &r2.corners(), T::ZERO)
+            }
+        };
+    }
+
+    /**
+     * Normal, depth and up to two points of the contact, None if the shapes do not collide.
+     * Moving the given shape by contact.separation() pushes it out of this one.
+     * Dots and segments that only share a point get a contact of zero depth.
+     */
+    pub fn contact_with(&self, shape: &Shape<T>) -> Option<Contact<T>> {
+        let a = self.hull();
+        let b = shape.hull();
+
+        if let Option::Some(p) = sat::penetration(a.points(), a.radius, b.points(), b.radius) {
+            let (points, point_count) = p.manifold(a.points(), a.radius, b.points(), b.radius);
+            return Option::Some(Contact {
+                normal: p.normal,
+                depth: p.depth,
+                points,
+                point_count,
+            });
+        }
+
+        // shapes without area can touch without penetrating
+        let thin = |h: &sat::Hull<T>| h.radius == T::ZERO && h.points().len() <= 2;
+        if !thin(&a) || !thin(&b) {
+            return Option::None;
+        }
+        let point = self.collision_with(shape)?;
+        // no direction to push along, the normal of a segment is as good as any
+        let mut normal = match (self, shape) {
+            (Shape::Segment(s), _) | (_, Shape::Segment(s)) => s.vector.perp_left(),
+            _ => Vector(T::ZERO, T::ZERO),
+        };
+        normal.normalize();
+        if normal.size() == T::ZERO {
+            normal = Vector(T::ZERO, T::ONE);
+        }
+        return Option::Some(Contact {
+            normal,
+            depth: T::ZERO,
+            points: [point; 2],
+            point_count: 1,
+        });
+    }
+
+    /**
+     * Where the ray enters the shape, see RayHit for rays starting inside
+     */
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<RayHit<T>> {
+        return match self {
+            Shape::Dot(p) => Shape::dot_raycast(p, ray),
+            Shape::Rectangle(r) => r.raycast(ray),
+            Shape::Circle(c) => c.raycast(ray),
+            Shape::Segment(s) => s.raycast(ray),
+            Shape::Polygon(p) => p.raycast(ray),
+            Shape::Capsule(c) => c.raycast(ray),
+            Shape::OrientedRectangle(r) => r.raycast(ray),
+        };
+    }
+
+    /**
+     * Point of the shape nearest to p, p itself if it is inside
+     */
+    pub fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return match self {
+            Shape::Dot(d) => *d,
+            Shape::Rectangle(r) => r.closest_point(p),
+            Shape::Circle(c) => c.closest_point(p),
+            Shape::Segment(s) => s.closest_point(p),
+            Shape::Polygon(poly) => poly.closest_point(p),
+            Shape::Capsule(c) => c.closest_point(p),
+            Shape::OrientedRectangle(r) => r.closest_point(p),
+        };
+    }
+
+    pub fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.closest_point(p).distance(p);
+    }
+
+    /**
+     * Distance to the surface, negative inside. Dots and segments have no inside.
+     */
+    pub fn signed_distance(&self, p: &Vector<T>) -> T {
+        return match self {
+            Shape::Dot(d) => d.distance(p),
+            Shape::Rectangle(r) => r.signed_distance(p),
+            Shape::Circle(c) => c.signed_distance(p),
+            Shape::Segment(s) => s.signed_distance(p),
+            Shape::Polygon(poly) => poly.signed_distance(p),
+            Shape::Capsule(c) => c.signed_distance(p),
+            Shape::OrientedRectangle(r) => r.signed_distance(p),
         };
     }
 
-    fn circles_collision(a: &Circle, b: &Circle) -> Option<Vector> {
-        let ab = a.location.clone_sub(&b.location);
+    /**
+     * Smallest gap between the two shapes, zero if they touch or overlap
+     */
+    pub fn distance_to(&self, shape: &Shape<T>) -> T {
+        let a = self.hull();
+        let b = shape.hull();
+        return sat::distance(a.points(), a.radius, b.points(), b.radius);
+    }
+
+    /**
+     * Convex hull and rounding radius, the form the separating axis test works on
+     */
+    pub(crate) fn hull(&self) -> sat::Hull<T> {
+        return match self {
+            Shape::Dot(p) => sat::Hull::new(&[*p], T::ZERO),
+            Shape::Rectangle(r) => sat::Hull::new(Polygon::from_rectangle(r).vertices(), T::ZERO),
+            Shape::Circle(c) => sat::Hull::new(&[c.location], c.radius),
+            Shape::Segment(s) => sat::Hull::new(&[s.location, s.end()], T::ZERO),
+            Shape::Polygon(p) => sat::Hull::new(p.vertices(), T::ZERO),
+            Shape::Capsule(c) => sat::Hull::new(&c.ends(), c.radius),
+            Shape::OrientedRectangle(r) => sat::Hull::new(&r.corners(), T::ZERO),
+        };
+    }
+
+    fn circles_collision(a: &Circle<T>, b: &Circle<T>) -> Option<Vector<T>> {
+        let ab = a.location - b.location;
         let ab_size = ab.size();
-        if ab_size == 0. {
-            return Some(a.location.clone());
+        if ab_size == T::ZERO {
+            return Some(a.location);
         } else if ab_size > a.radius + b.radius {
             return Option::None;
         } else {
-            let ar = b
-                .location
-                .clone_sub(&a.location)
-                .clone_scale(a.radius / ab_size)
-                .clone_add(&a.location);
-            let br = a
-                .location
-                .clone_sub(&b.location)
-                .clone_scale(b.radius / ab_size)
-                .clone_add(&b.location);
+            let ar = (b.location - a.location) * (a.radius / ab_size) + a.location;
+            let br = (a.location - b.location) * (b.radius / ab_size) + b.location;
 
-            return Option::Some(Vector((ar.0 + br.0) / 2., (ar.1 + br.1) / 2.));
+            return Option::Some((ar + br) / T::from_f32(2.));
         }
     }
 
-    fn reactangles_collision(a: &Rectangle, b: &Rectangle) -> Option<Vector> {
+    fn reactangles_collision(a: &Rectangle<T>, b: &Rectangle<T>) -> Option<Vector<T>> {
         let a_frame = a.to_frame();
         let b_frame = b.to_frame();
 
@@ -108,20 +390,84 @@ impl Shape {
         }
     }
 
-    fn dot_in_circle(c: &Circle, d: &Vector) -> Option<Vector> {
-        let l = c.location.clone_sub(d).size();
+    /**
+     * Separating axis test, the collision point is halfway between the surfaces
+     * along the axis of least penetration
+     */
+    fn convex_collision(a: &[Vector<T>], a_radius: T, b: &[Vector<T>], b_radius: T) -> Option<Vector<T>> {
+        return sat::penetration(a, a_radius, b, b_radius).map(|p| p.contact(b, b_radius));
+    }
+
+    fn dot_in(inside: bool, d: &Vector<T>) -> Option<Vector<T>> {
+        if inside {
+            Option::Some(*d)
+        } else {
+            Option::None
+        }
+    }
+
+    fn dot_raycast(d: &Vector<T>, ray: &Ray<T>) -> Option<RayHit<T>> {
+        let to_dot = *d - ray.origin;
+        if to_dot.perp_dot(&ray.direction) != T::ZERO || to_dot.dot(&ray.direction) < T::ZERO {
+            return Option::None;
+        }
+        if ray.direction == Vector(T::ZERO, T::ZERO) {
+            return if *d == ray.origin { ray.inside() } else { Option::None };
+        }
+        return ray.hit(to_dot.dot(&ray.direction) / ray.direction.size_squared(), -ray.direction);
+    }
+
+    fn dot_in_circle(c: &Circle<T>, d: &Vector<T>) -> Option<Vector<T>> {
+        let l = (c.location - *d).size();
 
         if l < c.radius {
-            Option::Some(d.clone())
+            Option::Some(*d)
         } else {
             Option::None
         }
     }
 }
 
+impl<T: Scalar> SupportMap<T> for Shape<T> {
+    fn support(&self, direction: &Vector<T>) -> Vector<T> {
+        return match self {
+            Shape::Dot(p) => *p,
+            Shape::Rectangle(r) => r.support(direction),
+            Shape::Circle(c) => c.support(direction),
+            Shape::Segment(s) => s.support(direction),
+            Shape::Polygon(p) => p.support(direction),
+            Shape::Capsule(c) => c.support(direction),
+            Shape::OrientedRectangle(r) => r.support(direction),
+        };
+    }
+}
+
+impl<T> ApproxEq for Shape<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Shape<T>, tolerance: Tolerance<T>) -> bool {
+        return match (self, other) {
+            (Shape::Dot(a), Shape::Dot(b)) => a.approx_eq(b, tolerance),
+            (Shape::Rectangle(a), Shape::Rectangle(b)) => a.approx_eq(b, tolerance),
+            (Shape::Circle(a), Shape::Circle(b)) => a.approx_eq(b, tolerance),
+            (Shape::Segment(a), Shape::Segment(b)) => a.approx_eq(b, tolerance),
+            (Shape::Polygon(a), Shape::Polygon(b)) => a.approx_eq(b, tolerance),
+            (Shape::Capsule(a), Shape::Capsule(b)) => a.approx_eq(b, tolerance),
+            (Shape::OrientedRectangle(a), Shape::OrientedRectangle(b)) => a.approx_eq(b, tolerance),
+            _ => false,
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::gjk;
+    use vector::polar::Angle;
+    use vector::{assert_approx_eq, assert_approx_ne};
 
     #[test]
     fn dot_to_frame() {
@@ -158,4 +504,687 @@ mod tests {
         assert_eq!(a.collision_with(&b), Option::Some(Vector(0.5, 0.5)));
         assert_eq!(a.collision_with(&c), Option::None);
     }
+
+    #[test]
+    fn circles_collision_double_precision() {
+        let a: Shape<f64> = Shape::Circle(Circle {
+            location: Vector(1e9, 1e9),
+            radius: 1.0,
+        });
+        let b = Shape::Circle(Circle {
+            location: Vector(1e9 + 1.5, 1e9),
+            radius: 1.0,
+        });
+
+        assert_eq!(a.collision_with(&b), Option::Some(Vector(1e9 + 0.75, 1e9)));
+    }
+
+    fn slope() -> Shape {
+        // right triangle with the slope facing up-left
+        return Shape::Polygon(Polygon::new(&[Vector(0., 0.), Vector(4., 0.), Vector(4., 4.)]).unwrap());
+    }
+
+    #[test]
+    fn polygon_and_dot_collision() {
+        let p = slope();
+
+        assert_eq!(p.collision_with(&Shape::Dot(Vector(3., 1.))), Option::Some(Vector(3., 1.)));
+        assert_eq!(Shape::Dot(Vector(1., 3.)).collision_with(&p), Option::None);
+    }
+
+    #[test]
+    fn polygon_and_circle_collision() {
+        let p = slope();
+        let c = Shape::Circle(Circle {
+            location: Vector(1., 2.),
+            radius: 1.,
+        });
+
+        // halfway between the slope at (1.5, 1.5) and the deepest point of the circle
+        let deepest = Vector(1., 2.) + Vector(1., -1.) * 0.5f32.sqrt();
+        let expected = (Vector(1.5, 1.5) + deepest) / 2.;
+        assert_approx_eq!(p.collision_with(&c).unwrap(), expected, Tolerance::Absolute(1e-6));
+        assert_approx_eq!(c.collision_with(&p).unwrap(), expected, Tolerance::Absolute(1e-6));
+
+        let far = Shape::Circle(Circle {
+            location: Vector(1., 3.),
+            radius: 1.,
+        });
+        assert_eq!(p.collision_with(&far), Option::None);
+    }
+
+    #[test]
+    fn polygon_and_rectangle_collision() {
+        let p = slope();
+        let r = Shape::Rectangle(Rectangle {
+            location: Vector(5., 2.),
+            dementions: Vector(3., 1.),
+        });
+
+        assert_eq!(p.collision_with(&r), Option::Some(Vector(3.75, 2.)));
+        assert_eq!(r.collision_with(&p), Option::Some(Vector(3.75, 2.)));
+
+        // inside the bounding frame of the triangle, but above the slope
+        let above = Shape::Rectangle(Rectangle {
+            location: Vector(1., 3.),
+            dementions: Vector(1., 1.),
+        });
+        assert_eq!(p.collision_with(&above), Option::None);
+    }
+
+    #[test]
+    fn polygon_and_segment_collision() {
+        let p = slope();
+        let s = Shape::Segment(Segment {
+            location: Vector(2., -1.),
+            vector: Vector(0., 1.5),
+        });
+
+        assert_eq!(p.collision_with(&s), Option::Some(Vector(2., 0.25)));
+        assert_eq!(s.collision_with(&p), Option::Some(Vector(2., 0.25)));
+
+        let parallel = Shape::Segment(Segment {
+            location: Vector(0., 1.),
+            vector: Vector(2., 2.),
+        });
+        assert_eq!(p.collision_with(&parallel), Option::None);
+    }
+
+    #[test]
+    fn polygons_collision() {
+        let a = slope();
+        let mut b = slope();
+        b.shift(&Vector(-3., 0.));
+
+        // overlap is the triangle (0, 0), (1, 0), (1, 1), least penetration along its diagonal
+        assert_approx_eq!(a.collision_with(&b), Option::Some(Vector(0.75, 0.25)));
+
+        b.shift(&Vector(-1.5, 0.));
+        assert_eq!(a.collision_with(&b), Option::None);
+        assert_eq!(b.to_frame(), Frame { from: Vector(-4.5, 0.), to: Vector(-0.5, 4.) });
+    }
+
+    fn character() -> Shape {
+        return Shape::Capsule(Capsule {
+            segment: Segment {
+                location: Vector(0., 0.),
+                vector: Vector(0., 2.),
+            },
+            radius: 1.,
+        });
+    }
+
+    #[test]
+    fn capsule_and_dot_collision() {
+        let c = character();
+
+        assert_eq!(c.collision_with(&Shape::Dot(Vector(0., 2.5))), Option::Some(Vector(0., 2.5)));
+        assert_eq!(Shape::Dot(Vector(0.9, 2.9)).collision_with(&c), Option::None);
+    }
+
+    #[test]
+    fn capsule_and_circle_collision() {
+        let c = character();
+        let near = Shape::Circle(Circle {
+            location: Vector(1.5, 1.),
+            radius: 1.,
+        });
+        let far = Shape::Circle(Circle {
+            location: Vector(1.5, 3.5),
+            radius: 1.,
+        });
+
+        assert_eq!(c.collision_with(&near), Option::Some(Vector(0.75, 1.)));
+        assert_eq!(near.collision_with(&c), Option::Some(Vector(0.75, 1.)));
+        // inside the bounding frames, but past the rounded top
+        assert_eq!(c.collision_with(&far), Option::None);
+    }
+
+    #[test]
+    fn capsule_on_tile_seam() {
+        let mut c = character();
+        c.shift(&Vector(1., 0.9));
+        let left = Shape::Rectangle(Rectangle {
+            location: Vector(0.5, -0.5),
+            dementions: Vector(1., 1.),
+        });
+        let right = Shape::Rectangle(Rectangle {
+            location: Vector(1.5, -0.5),
+            dementions: Vector(1., 1.),
+        });
+
+        // both tiles push straight up, the seam between them has no corner to catch on
+        let expected = Option::Some(Vector(1., -0.05));
+        assert_approx_eq!(c.collision_with(&left), expected, Tolerance::Absolute(1e-6));
+        assert_approx_eq!(right.collision_with(&c), expected, Tolerance::Absolute(1e-6));
+    }
+
+    #[test]
+    fn capsule_and_segment_collision() {
+        let c = character();
+        let wall = |x: f32| {
+            Shape::Segment(Segment {
+                location: Vector(x, 0.),
+                vector: Vector(0., 2.),
+            })
+        };
+
+        assert_eq!(c.collision_with(&wall(0.8)), Option::Some(Vector(0.9, 1.)));
+        assert_eq!(wall(0.8).collision_with(&c), Option::Some(Vector(0.9, 1.)));
+        assert_eq!(c.collision_with(&wall(1.5)), Option::None);
+    }
+
+    #[test]
+    fn capsules_collision() {
+        let a = character();
+        let mut b = character();
+        b.shift(&Vector(1.5, 0.));
+
+        assert_eq!(a.collision_with(&b), Option::Some(Vector(0.75, 1.)));
+
+        // diagonal neighbour only reaches into the bounding frame
+        b.shift(&Vector(0., 3.4));
+        assert_eq!(a.collision_with(&b), Option::None);
+    }
+
+    fn crate_box() -> Shape {
+        // square standing on its corner
+        return Shape::OrientedRectangle(OrientedRectangle {
+            location: Vector(0., 0.),
+            dementions: Vector(2., 2.),
+            rotation: Angle(core::f32::consts::FRAC_PI_4),
+        });
+    }
+
+    #[test]
+    fn oriented_rectangle_and_dot_collision() {
+        let b = crate_box();
+
+        assert_eq!(b.collision_with(&Shape::Dot(Vector(0.5, 0.5))), Option::Some(Vector(0.5, 0.5)));
+        assert_eq!(Shape::Dot(Vector(0.8, 0.8)).collision_with(&b), Option::None);
+    }
+
+    #[test]
+    fn oriented_rectangle_and_rectangle_collision() {
+        let b = crate_box();
+        let touching = Shape::Rectangle(Rectangle {
+            location: Vector(2., 0.),
+            dementions: Vector(2., 2.),
+        });
+        let expected = Vector(1. + (2f32.sqrt() - 1.) / 2., 0.);
+
+        assert_approx_eq!(b.collision_with(&touching), Option::Some(expected), Tolerance::Absolute(1e-6));
+        assert_approx_eq!(touching.collision_with(&b), Option::Some(expected), Tolerance::Absolute(1e-6));
+
+        // the bounding frames overlap, the rotated box does not
+        let corner = Shape::Rectangle(Rectangle {
+            location: Vector(1.3, 1.3),
+            dementions: Vector(1., 1.),
+        });
+        assert!(b.to_frame().is_intercected(&corner.to_frame()));
+        assert_eq!(b.collision_with(&corner), Option::None);
+    }
+
+    #[test]
+    fn oriented_rectangle_and_other_shapes_collision() {
+        let b = crate_box();
+        let circle = |x: f32| Shape::Circle(Circle { location: Vector(x, x), radius: 0.8 });
+        let segment = |x: f32| Shape::Segment(Segment { location: Vector(x, -2.), vector: Vector(0., 4.) });
+        let mut other = crate_box();
+        other.shift(&Vector(2.5, 0.));
+
+        assert!(b.collision_with(&circle(1.)).is_some());
+        assert_eq!(b.collision_with(&circle(1.5)), Option::None);
+        assert!(segment(1.).collision_with(&b).is_some());
+        assert_eq!(segment(1.5).collision_with(&b), Option::None);
+        assert!(b.collision_with(&other).is_some());
+        other.shift(&Vector(0.5, 0.));
+        assert_eq!(b.collision_with(&other), Option::None);
+        assert!(b.collision_with(&character()).is_some());
+        assert!(slope().collision_with(&b).is_some());
+    }
+
+    // one shape of every variant, each of them has the origin inside (or on it, for dot and segment)
+    fn every_variant() -> Vec<Shape> {
+        return vec![
+            Shape::Dot(Vector(0., 0.)),
+            Shape::Rectangle(Rectangle {
+                location: Vector(0., 0.),
+                dementions: Vector(2., 2.),
+            }),
+            Shape::Circle(Circle {
+                location: Vector(0., 0.),
+                radius: 1.,
+            }),
+            Shape::Segment(Segment {
+                location: Vector(-1., 0.),
+                vector: Vector(2., 0.),
+            }),
+            Shape::Polygon(Polygon::new(&[Vector(-1., -1.), Vector(1., -1.), Vector(0., 1.)]).unwrap()),
+            Shape::Capsule(Capsule {
+                segment: Segment {
+                    location: Vector(0., -0.5),
+                    vector: Vector(0., 1.),
+                },
+                radius: 0.5,
+            }),
+            Shape::OrientedRectangle(OrientedRectangle {
+                location: Vector(0., 0.),
+                dementions: Vector(2., 1.),
+                rotation: Angle(0.3),
+            }),
+        ];
+    }
+
+    #[test]
+    fn collision_matrix() {
+        let shapes = every_variant();
+
+        for a in &shapes {
+            for b in &shapes {
+                let hit = a.collision_with(b);
+                assert!(hit.is_some(), "{:?} and {:?} should collide", a, b);
+                assert_approx_eq!(hit, b.collision_with(a), Tolerance::Absolute(1e-6));
+
+                let mut far = *b;
+                far.shift(&Vector(10., 10.));
+                assert_eq!(a.collision_with(&far), Option::None, "{:?} and {:?}", a, far);
+                assert_eq!(far.collision_with(a), Option::None, "{:?} and {:?}", far, a);
+            }
+        }
+    }
+
+    #[test]
+    fn collision_table() {
+        let square = Shape::Rectangle(Rectangle {
+            location: Vector(0., 0.),
+            dementions: Vector(2., 2.),
+        });
+        let floor = Shape::Segment(Segment {
+            location: Vector(-1., 0.),
+            vector: Vector(2., 0.),
+        });
+        let circle = |x: f32, y: f32, radius: f32| Shape::Circle(Circle { location: Vector(x, y), radius });
+        let segment = |x: f32, y: f32, dx: f32, dy: f32| {
+            Shape::Segment(Segment {
+                location: Vector(x, y),
+                vector: Vector(dx, dy),
+            })
+        };
+
+        // the "near" cases overlap in their bounding frames, but not in their shapes
+        let table = [
+            // dot and rectangle, the boundary is not inside
+            (Shape::Dot(Vector(0.5, 0.5)), square, Option::Some(Vector(0.5, 0.5))),
+            (Shape::Dot(Vector(1., 0.5)), square, Option::None),
+            // dot and segment
+            (Shape::Dot(Vector(0.5, 0.)), floor, Option::Some(Vector(0.5, 0.))),
+            (Shape::Dot(Vector(1., 0.)), floor, Option::Some(Vector(1., 0.))),
+            (Shape::Dot(Vector(0.5, 0.1)), floor, Option::None),
+            // circle and rectangle
+            (circle(2., 0., 1.5), square, Option::Some(Vector(0.75, 0.))),
+            (circle(1.8, 1.8, 1.), square, Option::None),
+            // circle and segment
+            (circle(0., 0.5, 1.), floor, Option::Some(Vector(0., -0.25))),
+            (circle(1.6, 0.6, 0.7), floor, Option::None),
+            // rectangle and segment
+            (square, segment(0.5, 0.2, 3., 0.), Option::Some(Vector(0.75, 0.2))),
+            (square, segment(0.5, 2., 1.5, -1.5), Option::None),
+            // segment and segment
+            (segment(-1., -1., 2., 2.), segment(-1., 1., 2., -2.), Option::Some(Vector(0., 0.))),
+            (segment(0., 0., 0., 1.), floor, Option::Some(Vector(0., 0.))),
+            (segment(0., 0., 2., 0.), segment(1., 0., 2., 0.), Option::Some(Vector(1.5, 0.))),
+            (segment(0., 0., 2., 2.), segment(1.5, 0., 0.5, 1.), Option::None),
+        ];
+
+        for (a, b, expected) in table {
+            assert_approx_eq!(a.collision_with(&b), expected, Tolerance::Absolute(1e-6));
+            assert_approx_eq!(b.collision_with(&a), expected, Tolerance::Absolute(1e-6));
+        }
+    }
+
+    #[test]
+    fn box_resting_on_floor() {
+        let floor = Shape::Rectangle(Rectangle {
+            location: Vector(0., -1.),
+            dementions: Vector(10., 2.),
+        });
+        let b = Shape::Rectangle(Rectangle {
+            location: Vector(1., 0.9),
+            dementions: Vector(2., 2.),
+        });
+
+        let c = floor.contact_with(&b).unwrap();
+        assert_eq!(c.normal, Vector(0., 1.));
+        assert_approx_eq!(c.depth, 0.1);
+        assert_eq!(c.point_count, 2);
+        assert_approx_eq!(c.points()[0], Vector(2., -0.05));
+        assert_approx_eq!(c.points()[1], Vector(0., -0.05));
+
+        // the other way around only the direction changes
+        let flipped = b.contact_with(&floor).unwrap();
+        assert_approx_eq!(flipped.normal, -c.normal);
+        assert_approx_eq!(flipped.depth, c.depth);
+    }
+
+    #[test]
+    fn circles_contact() {
+        let a = Shape::Circle(Circle {
+            location: Vector(0., 0.),
+            radius: 1.,
+        });
+        let b = Shape::Circle(Circle {
+            location: Vector(1.5, 0.),
+            radius: 1.,
+        });
+
+        let c = a.contact_with(&b).unwrap();
+        assert_eq!(c.normal, Vector(1., 0.));
+        assert_eq!(c.depth, 0.5);
+        assert_eq!(c.points(), &[a.collision_with(&b).unwrap()]);
+
+        // concentric circles still get pushed apart
+        let c = a.contact_with(&a).unwrap();
+        assert_eq!(c.depth, 2.);
+        assert_eq!(c.normal.size(), 1.);
+    }
+
+    #[test]
+    fn contact_matrix() {
+        let shapes = every_variant();
+
+        for a in &shapes {
+            for b in &shapes {
+                let c = a.contact_with(b).unwrap();
+                assert!(c.point_count >= 1);
+                assert_approx_eq!(c.normal.size(), 1., Tolerance::Absolute(1e-6));
+                assert!(c.depth >= 0.);
+
+                // pushing b out by the separation leaves the shapes at most touching
+                let mut pushed = *b;
+                pushed.shift(&(c.separation() * 1.001));
+                if c.depth > 0. {
+                    assert_eq!(a.contact_with(&pushed), Option::None, "{:?} and {:?}", a, pushed);
+                }
+
+                let swapped = b.contact_with(a).unwrap();
+                assert_approx_eq!(swapped.depth, c.depth, Tolerance::Absolute(1e-6));
+
+                let mut far = *b;
+                far.shift(&Vector(10., 10.));
+                assert_eq!(a.contact_with(&far), Option::None);
+            }
+        }
+    }
+
+    #[test]
+    fn crossing_segments_contact() {
+        let a = Shape::Segment(Segment {
+            location: Vector(0., 0.),
+            vector: Vector(2., 0.),
+        });
+        let b = Shape::Segment(Segment {
+            location: Vector(1., 0.),
+            vector: Vector(3., 0.),
+        });
+
+        let c = a.contact_with(&b).unwrap();
+        assert_eq!(c.depth, 0.);
+        assert_eq!(c.normal, Vector(0., 1.));
+        assert_eq!(c.points(), &[Vector(1.5, 0.)]);
+        assert_eq!(Shape::Dot(Vector(5., 0.)).contact_with(&a), Option::None);
+    }
+
+    #[test]
+    fn raycast() {
+        let ray = Ray::new(Vector(-5., 0.), Vector(1., 0.));
+        let expected = [
+            (Shape::Dot(Vector(0., 0.)), 5., Vector(-1., 0.)),
+            (Shape::Rectangle(Rectangle { location: Vector(0., 0.), dementions: Vector(2., 2.) }), 4., Vector(-1., 0.)),
+            (Shape::Circle(Circle { location: Vector(0., 0.), radius: 1. }), 4., Vector(-1., 0.)),
+            (Shape::Segment(Segment { location: Vector(-1., -1.), vector: Vector(0., 2.) }), 4., Vector(-1., 0.)),
+            (slope(), 5., Vector(-0.70710677, 0.70710677)),
+            (character(), 4., Vector(-1., 0.)),
+            (
+                Shape::OrientedRectangle(OrientedRectangle {
+                    location: Vector(0., 0.),
+                    dementions: Vector(2., 4.),
+                    rotation: Angle(core::f32::consts::FRAC_PI_2),
+                }),
+                3.,
+                Vector(-1., 0.),
+            ),
+        ];
+
+        for (shape, distance, normal) in expected {
+            let hit = shape.raycast(&ray).unwrap();
+            assert_approx_eq!(hit.distance, distance, Tolerance::Absolute(1e-5));
+            assert_approx_eq!(hit.point, ray.point_at(distance), Tolerance::Absolute(1e-5));
+            assert_approx_eq!(hit.normal, normal, Tolerance::Absolute(1e-5));
+
+            assert_eq!(shape.raycast(&ray.with_max_distance(distance - 0.1)), Option::None);
+            let mut away = ray;
+            away.direction = -away.direction;
+            assert_eq!(shape.raycast(&away), Option::None, "{:?}", shape);
+        }
+    }
+
+    #[test]
+    fn raycast_from_inside() {
+        for shape in every_variant() {
+            let hit = shape.raycast(&Ray::new(Vector(0., 0.), Vector(0., 2.))).unwrap();
+            assert_eq!(hit.distance, 0.);
+            assert_eq!(hit.point, Vector(0., 0.));
+        }
+    }
+
+    #[test]
+    fn point_distance() {
+        let p = Vector(3., 0.);
+        for shape in every_variant() {
+            let closest = shape.closest_point(&p);
+            assert_approx_eq!(shape.distance_to_point(&p), closest.distance(&p), Tolerance::Absolute(1e-6));
+            assert_approx_eq!(shape.signed_distance(&p), shape.distance_to_point(&p), Tolerance::Absolute(1e-6));
+            assert!(shape.distance_to_point(&p) >= 1.5, "{:?}", shape);
+
+            // the origin is inside or on every one of them
+            assert_eq!(shape.closest_point(&Vector(0., 0.)), Vector(0., 0.));
+            assert!(shape.signed_distance(&Vector(0., 0.)) <= 0.);
+        }
+
+        assert_eq!(character().signed_distance(&Vector(0., 1.)), -1.);
+        assert_eq!(slope().closest_point(&Vector(5., 1.)), Vector(4., 1.));
+    }
+
+    #[test]
+    fn shapes_distance() {
+        let shapes = every_variant();
+        for a in &shapes {
+            for b in &shapes {
+                assert_eq!(a.distance_to(b), 0.);
+
+                let mut far = *b;
+                far.shift(&Vector(10., 0.));
+                let d = a.distance_to(&far);
+                assert!((7. ..=10.).contains(&d), "{:?} and {:?}: {}", a, far, d);
+                assert_approx_eq!(d, far.distance_to(a), Tolerance::Absolute(1e-5));
+            }
+        }
+
+        let circle = Shape::Circle(Circle { location: Vector(10., 1.), radius: 2. });
+        assert_eq!(character().distance_to(&circle), 7.);
+        assert_approx_eq!(slope().distance_to(&Shape::Dot(Vector(0., 4.))), 8f32.sqrt());
+    }
+
+    #[test]
+    fn gjk_matches_specialized() {
+        let shapes = every_variant();
+        let offsets = [Vector(0., 0.), Vector(0.3, 0.2), Vector(1.2, -0.4), Vector(3., 1.), Vector(-2.5, 2.)];
+
+        for a in &shapes {
+            for b in &shapes {
+                for offset in offsets {
+                    let mut b = *b;
+                    b.shift(&offset);
+                    let context = format!("{:?} and {:?}", a, b);
+
+                    let expected = a.distance_to(&b);
+                    assert_approx_eq!(gjk::distance(a, &b), expected, Tolerance::Absolute(1e-3));
+                    if expected > 1e-3 {
+                        assert!(!gjk::intersects(a, &b), "{}", context);
+                    }
+
+                    match (a.contact_with(&b), gjk::penetration(a, &b)) {
+                        (Option::Some(sat), Option::Some(epa)) => {
+                            assert!(gjk::intersects(a, &b), "{}", context);
+                            assert_approx_eq!(epa.depth, sat.depth, Tolerance::Absolute(1e-3));
+                            assert_approx_eq!(epa.normal.size(), 1., Tolerance::Absolute(1e-6));
+                        }
+                        // shapes without area only touch
+                        (Option::Some(sat), Option::None) => assert_eq!(sat.depth, 0., "{}", context),
+                        (Option::None, Option::Some(epa)) => assert!(epa.depth < 1e-3, "{}", context),
+                        (Option::None, Option::None) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn area() {
+        let pi = core::f32::consts::PI;
+        let expected = [0., 4., pi, 0., 2., 1. + pi / 4., 2.];
+        for (shape, area) in every_variant().iter().zip(expected) {
+            assert_approx_eq!(shape.area(), area);
+        }
+    }
+
+    #[test]
+    fn transform() {
+        let t = Transform2D::from_trs(Vector(10., 0.), std::f32::consts::FRAC_PI_2, Vector(1., 1.));
+
+        let mut dot = Shape::Dot(Vector(1., 0.));
+        dot.transform(&t);
+        assert!((dot.to_frame().from - Vector(10., 1.)).size() < 1e-5);
+
+        let mut segment = Shape::Segment(Segment {
+            location: Vector(0., 0.),
+            vector: Vector(2., 0.),
+        });
+        let frame = segment.transformed_frame(&t);
+        segment.transform(&t);
+        assert_eq!(segment.to_frame(), frame);
+        assert!((frame.to - Vector(10., 2.)).size() < 1e-5);
+
+        let rectangle = Shape::Rectangle(Rectangle {
+            location: Vector(0., 0.),
+            dementions: Vector(2., 2.),
+        });
+        let rotated = rectangle.transformed_frame(&Transform2D::from_rotation(std::f32::consts::FRAC_PI_4));
+        assert!((rotated.dementions() - Vector(8f32.sqrt(), 8f32.sqrt())).size() < 1e-5);
+    }
+
+    #[test]
+    fn transform_rectangle() {
+        let rectangle = Shape::Rectangle(Rectangle {
+            location: Vector(1., 0.),
+            dementions: Vector(2., 4.),
+        });
+
+        let mut scaled = rectangle;
+        scaled.transform(&Transform2D::from_trs(Vector(1., 1.), 0., Vector(-2., 1.)));
+        assert_eq!(scaled, Shape::Rectangle(Rectangle { location: Vector(-1., 1.), dementions: Vector(4., 4.) }));
+
+        let mut rotated = rectangle;
+        rotated.transform(&Transform2D::from_rotation(std::f32::consts::FRAC_PI_4));
+        let Shape::OrientedRectangle(r) = rotated else {
+            panic!("rotated rectangle stays axis-aligned: {:?}", rotated);
+        };
+        assert_approx_eq!(r.location, Vector(0.70710677, 0.70710677));
+        assert_approx_eq!(r.dementions, Vector(2., 4.));
+        assert_approx_eq!(r.rotation.0, std::f32::consts::FRAC_PI_4);
+        assert_eq!(rotated.to_frame(), rectangle.transformed_frame(&Transform2D::from_rotation(std::f32::consts::FRAC_PI_4)));
+    }
+
+    #[test]
+    fn approx_eq() {
+        let a = Shape::Circle(Circle {
+            location: Vector(0.1f64 + 0.2, 0.),
+            radius: 1.,
+        });
+        let b = Shape::Circle(Circle {
+            location: Vector(0.3, 0.),
+            radius: 1.,
+        });
+
+        assert_approx_eq!(a, b);
+        assert_approx_ne!(a, Shape::Dot(Vector(0.3, 0.)));
+        assert_approx_eq!(
+            Shape::Rectangle(Rectangle { location: Vector(0., 0.), dementions: Vector(1., 1.) }),
+            Shape::Rectangle(Rectangle { location: Vector(0.05, 0.), dementions: Vector(1., 1.) }),
+            Tolerance::Absolute(0.1)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let shapes: Vec<Shape> = vec![
+            Shape::Dot(Vector(1., 2.)),
+            Shape::Rectangle(Rectangle {
+                location: Vector(0., 0.),
+                dementions: Vector(3., 4.),
+            }),
+            Shape::Circle(Circle {
+                location: Vector(1., 2.),
+                radius: 3.,
+            }),
+            Shape::Segment(Segment {
+                location: Vector(1., 2.),
+                vector: Vector(3., -4.),
+            }),
+            Shape::Polygon(Polygon::new(&[Vector(0., 0.), Vector(4., 0.), Vector(4., 4.)]).unwrap()),
+            Shape::Capsule(Capsule {
+                segment: Segment {
+                    location: Vector(1., 2.),
+                    vector: Vector(0., 2.),
+                },
+                radius: 0.5,
+            }),
+            Shape::OrientedRectangle(OrientedRectangle {
+                location: Vector(1., 2.),
+                dementions: Vector(3., 4.),
+                rotation: Angle(0.5),
+            }),
+        ];
+
+        for s in shapes {
+            let json = serde_json::to_string(&s).unwrap();
+            assert_eq!(serde_json::from_str::<Shape>(&json).unwrap(), s);
+
+            let ron = ron::to_string(&s).unwrap();
+            assert_eq!(ron::from_str::<Shape>(&ron).unwrap(), s);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn contact_serde() {
+        let c = crate_box().contact_with(&character()).unwrap();
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(serde_json::from_str::<Contact>(&json).unwrap(), c);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_format() {
+        let c: Shape = serde_json::from_str(r#"{"circle": {"location": [1, 2], "radius": 3}}"#).unwrap();
+        assert_eq!(c, Shape::Circle(Circle {
+            location: Vector(1., 2.),
+            radius: 3.,
+        }));
+
+        let d = Shape::Dot(Vector(1., 2.));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#"{"dot":[1.0,2.0]}"#);
+    }
 }
@@ -0,0 +1,458 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::vector::{Orientation, Vector};
+
+use crate::frame::Frame;
+use crate::polygon::{Polygon, PolygonError, MAX_POLYGON_VERTICES};
+use crate::segment::Segment;
+use crate::shape::Shape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimplePolygonError {
+    TooFewVertices,
+    // repeated consecutive vertices or all vertices on one line
+    Degenerate,
+    // two edges cross or touch
+    SelfIntersecting,
+}
+
+impl fmt::Display for SimplePolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            SimplePolygonError::TooFewVertices => write!(f, "polygon needs at least 3 vertices"),
+            SimplePolygonError::Degenerate => write!(f, "polygon has no area or repeats a vertex"),
+            SimplePolygonError::SelfIntersecting => write!(f, "polygon edges intersect"),
+        };
+    }
+}
+
+/**
+ * Simple (not self-intersecting) polygon, possibly concave, like a terrain outline.
+ * Vertices are kept in counter-clockwise order.
+ * For collisions it is split into convex pieces, see `convex_decomposition`.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// the outline as a vertex list, self-intersecting outlines are rejected on load
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "Vec<Vector<T>>",
+        into = "Vec<Vector<T>>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct SimplePolygon<T = f32> {
+    vertices: Vec<Vector<T>>,
+}
+
+impl<T: Scalar> SimplePolygon<T> {
+    /**
+     * Validates the outline: clockwise input is reversed to counter-clockwise,
+     * no two edges may cross or touch except neighbours at their shared vertex
+     */
+    pub fn new(points: &[Vector<T>]) -> Result<SimplePolygon<T>, SimplePolygonError> {
+        let n = points.len();
+        if n < 3 {
+            return Err(SimplePolygonError::TooFewVertices);
+        }
+        if (0..n).any(|i| points[i] == points[(i + 1) % n])
+            || (2..n).all(|i| Vector::orientation(&points[0], &points[1], &points[i]) == Orientation::Collinear)
+        {
+            return Err(SimplePolygonError::Degenerate);
+        }
+
+        let v = points;
+        for i in 0..n {
+            let (a, b, c) = (v[i], v[(i + 1) % n], v[(i + 2) % n]);
+            // neighbouring edges only meet at the shared vertex, unless the outline turns back on itself
+            if Vector::orientation(&a, &b, &c) == Orientation::Collinear && (b - a).dot(&(c - b)) < T::ZERO {
+                return Err(SimplePolygonError::SelfIntersecting);
+            }
+            for j in i + 2..n {
+                if (j + 1) % n == i {
+                    continue;
+                }
+                let edge = Segment { location: a, vector: b - a };
+                let other = Segment { location: v[j], vector: v[(j + 1) % n] - v[j] };
+                if edge.intersection(&other).is_some() {
+                    return Err(SimplePolygonError::SelfIntersecting);
+                }
+            }
+        }
+
+        let mut polygon = SimplePolygon {
+            vertices: points.to_vec(),
+        };
+        let area = polygon.signed_area();
+        if area == T::ZERO {
+            return Err(SimplePolygonError::Degenerate);
+        }
+        if area < T::ZERO {
+            polygon.vertices.reverse();
+        }
+
+        return Ok(polygon);
+    }
+
+    pub fn vertices(&self) -> &[Vector<T>] {
+        return &self.vertices;
+    }
+
+    pub fn area(&self) -> T {
+        return self.signed_area();
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        let mut from = self.vertices[0];
+        let mut to = self.vertices[0];
+        for p in &self.vertices {
+            from = Vector(from.0.min(p.0), from.1.min(p.1));
+            to = Vector(to.0.max(p.0), to.1.max(p.1));
+        }
+        return Frame { from, to };
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        for p in self.vertices.iter_mut() {
+            *p += *offset;
+        }
+    }
+
+    /**
+     * Triangulation by ear clipping, triangles are counter-clockwise indices into `vertices`.
+     * Vertices lying on a straight part of the outline produce no triangle of their own.
+     */
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let v = &self.vertices;
+        let mut remaining: Vec<usize> = (0..v.len()).collect();
+        let mut triangles = Vec::with_capacity(v.len() - 2);
+
+        while remaining.len() > 3 {
+            let n = remaining.len();
+            let corner = |i: usize| (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+
+            // vertex in the middle of a straight run is not an ear, it just goes away
+            let straight = (0..n).find(|&i| {
+                let (a, b, c) = corner(i);
+                Vector::orientation(&v[a], &v[b], &v[c]) == Orientation::Collinear
+            });
+            if let Some(i) = straight {
+                remaining.remove(i);
+                continue;
+            }
+
+            let ear = (0..n).find(|&i| {
+                let (a, b, c) = corner(i);
+                Vector::orientation(&v[a], &v[b], &v[c]) == Orientation::Counterclockwise
+                    && !remaining
+                        .iter()
+                        .filter(|&&k| k != a && k != b && k != c)
+                        .any(|&k| in_triangle(&v[k], &v[a], &v[b], &v[c]))
+            });
+            // a simple polygon always has an ear, this only guards against a malformed outline
+            let Some(i) = ear else { break };
+
+            let (a, b, c) = corner(i);
+            triangles.push([a, b, c]);
+            remaining.remove(i);
+        }
+
+        if remaining.len() == 3 {
+            let (a, b, c) = (remaining[0], remaining[1], remaining[2]);
+            if Vector::orientation(&v[a], &v[b], &v[c]) == Orientation::Counterclockwise {
+                triangles.push([a, b, c]);
+            }
+        }
+
+        return triangles;
+    }
+
+    /**
+     * Splits the polygon into convex pieces (Hertel–Mehlhorn): the triangulation
+     * is merged across diagonals for as long as the merged piece stays convex and
+     * fits into MAX_POLYGON_VERTICES. At most four times the optimal number of pieces.
+     * Fails if Polygon::new rejects a piece; it tests convexity with the same exact
+     * orientation as the triangulation, so pieces of a valid outline always pass.
+     */
+    pub fn convex_decomposition(&self) -> Result<Vec<Polygon<T>>, PolygonError> {
+        let mut pieces: Vec<Vec<usize>> = self.triangulate().iter().map(|t| t.to_vec()).collect();
+
+        let mut merged = true;
+        while merged {
+            merged = false;
+            'search: for a in 0..pieces.len() {
+                for b in a + 1..pieces.len() {
+                    if let Some(piece) = self.merge(&pieces[a], &pieces[b]) {
+                        pieces[a] = piece;
+                        pieces.swap_remove(b);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        return pieces.iter().map(|piece| self.convex_piece(piece)).collect();
+    }
+
+    /**
+     * Convex pieces as shapes, ready for `Shape::collision_with`
+     */
+    pub fn to_shapes(&self) -> Result<Vec<Shape<T>>, PolygonError> {
+        return Ok(self.convex_decomposition()?.into_iter().map(Shape::Polygon).collect());
+    }
+
+    /**
+     * Joins two pieces sharing a diagonal, None if they do not share one
+     * or the result would not be a valid convex polygon
+     */
+    fn merge(&self, a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+        if a.len() + b.len() - 2 > MAX_POLYGON_VERTICES {
+            return None;
+        }
+
+        // the diagonal goes u -> v in a and v -> u in b
+        let i = (0..a.len()).find(|&i| {
+            let (u, v) = (a[i], a[(i + 1) % a.len()]);
+            (0..b.len()).any(|j| b[j] == v && b[(j + 1) % b.len()] == u)
+        })?;
+        let v = a[(i + 1) % a.len()];
+        let j = b.iter().position(|&k| k == v)?;
+
+        // walk a from v around to u, then b from u around to v, skipping the shared ends
+        let mut piece: Vec<usize> = (0..a.len()).map(|k| a[(i + 1 + k) % a.len()]).collect();
+        piece.extend((2..b.len()).map(|k| b[(j + k) % b.len()]));
+
+        return self.convex_piece(&piece).ok().map(|_| piece);
+    }
+
+    fn convex_piece(&self, piece: &[usize]) -> Result<Polygon<T>, PolygonError> {
+        let mut points = [Vector::default(); MAX_POLYGON_VERTICES];
+        for (p, &k) in points.iter_mut().zip(piece) {
+            *p = self.vertices[k];
+        }
+        return Polygon::new(&points[..piece.len()]);
+    }
+
+    fn signed_area(&self) -> T {
+        let origin = self.vertices[0];
+        let n = self.vertices.len();
+        let mut area = T::ZERO;
+        for i in 1..n - 1 {
+            area += (self.vertices[i] - origin).perp_dot(&(self.vertices[i + 1] - origin));
+        }
+        return area / T::from_f32(2.);
+    }
+}
+
+impl<T: Scalar> TryFrom<Vec<Vector<T>>> for SimplePolygon<T> {
+    type Error = SimplePolygonError;
+
+    fn try_from(points: Vec<Vector<T>>) -> Result<SimplePolygon<T>, SimplePolygonError> {
+        return SimplePolygon::new(&points);
+    }
+}
+
+impl<T: Scalar> From<SimplePolygon<T>> for Vec<Vector<T>> {
+    fn from(polygon: SimplePolygon<T>) -> Vec<Vector<T>> {
+        return polygon.vertices;
+    }
+}
+
+/**
+ * True if p is inside the counter-clockwise triangle or on its boundary
+ */
+fn in_triangle<T: Scalar>(p: &Vector<T>, a: &Vector<T>, b: &Vector<T>, c: &Vector<T>) -> bool {
+    return [(a, b), (b, c), (c, a)]
+        .iter()
+        .all(|(from, to)| Vector::orientation(from, to, p) != Orientation::Clockwise);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::Circle;
+    use vector::approx::Tolerance;
+    use vector::assert_approx_eq;
+
+    // U-shaped valley: two walls and a floor
+    fn valley() -> SimplePolygon {
+        return SimplePolygon::new(&[
+            Vector(0., 0.),
+            Vector(6., 0.),
+            Vector(6., 4.),
+            Vector(4., 4.),
+            Vector(4., 2.),
+            Vector(2., 2.),
+            Vector(2., 4.),
+            Vector(0., 4.),
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            SimplePolygon::<f32>::new(&[Vector(0., 0.), Vector(1., 0.)]),
+            Err(SimplePolygonError::TooFewVertices)
+        );
+        assert_eq!(
+            SimplePolygon::new(&[Vector(0., 0.), Vector(1., 1.), Vector(2., 2.)]),
+            Err(SimplePolygonError::Degenerate)
+        );
+        assert_eq!(
+            SimplePolygon::new(&[Vector(0., 0.), Vector(1., 0.), Vector(1., 0.), Vector(0., 1.)]),
+            Err(SimplePolygonError::Degenerate)
+        );
+
+        // bow tie
+        let bow_tie = [Vector(0., 0.), Vector(2., 2.), Vector(2., 0.), Vector(0., 2.)];
+        assert_eq!(SimplePolygon::new(&bow_tie), Err(SimplePolygonError::SelfIntersecting));
+
+        // two squares touching at a corner
+        let touching = [
+            Vector(0., 0.),
+            Vector(1., 0.),
+            Vector(1., 1.),
+            Vector(2., 1.),
+            Vector(2., 2.),
+            Vector(1., 2.),
+            Vector(1., 1.),
+            Vector(0., 1.),
+        ];
+        assert_eq!(SimplePolygon::new(&touching), Err(SimplePolygonError::SelfIntersecting));
+
+        // spike going back along its own edge
+        let spike = [Vector(0., 0.), Vector(2., 0.), Vector(1., 0.), Vector(0., 2.)];
+        assert_eq!(SimplePolygon::new(&spike), Err(SimplePolygonError::SelfIntersecting));
+    }
+
+    #[test]
+    fn clockwise_is_reversed() {
+        let p = SimplePolygon::new(&[Vector(0., 0.), Vector(0., 1.), Vector(1., 0.)]).unwrap();
+
+        assert_eq!(p.area(), 0.5);
+        assert_eq!(p.vertices(), &[Vector(1., 0.), Vector(0., 1.), Vector(0., 0.)]);
+    }
+
+    #[test]
+    fn triangulate() {
+        let p = valley();
+        let triangles = p.triangulate();
+
+        assert_eq!(triangles.len(), 6);
+        let area: f32 = triangles
+            .iter()
+            .map(|t| {
+                let v = p.vertices();
+                (v[t[1]] - v[t[0]]).perp_dot(&(v[t[2]] - v[t[0]])) / 2.
+            })
+            .sum();
+        assert_eq!(area, p.area());
+        assert_eq!(p.area(), 20.);
+    }
+
+    #[test]
+    fn triangulate_straight_runs() {
+        // square with extra vertices in the middle of two sides
+        let p = SimplePolygon::new(&[
+            Vector(0., 0.),
+            Vector(1., 0.),
+            Vector(2., 0.),
+            Vector(2., 2.),
+            Vector(0., 2.),
+            Vector(0., 1.),
+        ])
+        .unwrap();
+
+        assert_eq!(p.triangulate().len(), 2);
+    }
+
+    #[test]
+    fn convex_decomposition() {
+        let p = valley();
+        let pieces = p.convex_decomposition().unwrap();
+
+        // a U needs three convex pieces
+        assert_eq!(pieces.len(), 3);
+        let area: f32 = pieces.iter().map(|c| c.area()).sum();
+        assert_eq!(area, p.area());
+
+        let convex = SimplePolygon::new(&[Vector(0., 0.), Vector(2., 0.), Vector(3., 1.), Vector(2., 2.), Vector(0., 2.)]).unwrap();
+        assert_eq!(convex.convex_decomposition().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn nearly_collinear_decomposition() {
+        // bumps of 1e-4 on the floor are kept and the pieces still cover the outline
+        let bumpy = SimplePolygon::new(&[
+            Vector(1000., 1000.),
+            Vector(1001., 1000.0001),
+            Vector(1002., 1000.),
+            Vector(1003., 1000.0001),
+            Vector(1004., 1000.),
+            Vector(1004., 1001.),
+            Vector(1000., 1001.),
+        ])
+        .unwrap();
+        let area: f32 = bumpy.convex_decomposition().unwrap().iter().map(|c| c.area()).sum();
+        assert_eq!(area, bumpy.area());
+
+        // 0 -> b -> c turns left by less than a f32 cross product can see, the sliver stays a piece
+        let b: Vector = Vector(0.37246585, 1.502933);
+        let c: Vector = Vector(0.74493164, 3.005866);
+        let sliver = SimplePolygon::new(&[
+            b,
+            c,
+            c + Vector(1., -0.5),
+            c + Vector(1., 2.),
+            Vector(-1., c.1 + 2.),
+            Vector(-1., -1.),
+            Vector(0., 0.),
+        ])
+        .unwrap();
+        assert_eq!(Vector::orientation(&Vector(0., 0.), &b, &c), Orientation::Counterclockwise);
+        let pieces = sliver.convex_decomposition().unwrap();
+        assert!(pieces.iter().any(|p| p.vertices().contains(&b)));
+        let area: f32 = pieces.iter().map(|p| p.area()).sum();
+        assert_approx_eq!(area, sliver.area(), Tolerance::Absolute(1e-5));
+        assert_eq!(sliver.to_shapes().unwrap().len(), pieces.len());
+    }
+
+    #[test]
+    fn collision_with_pieces() {
+        let p = valley();
+        let ball = |x: f32, y: f32| Shape::Circle(Circle { location: Vector(x, y), radius: 0.5 });
+
+        // resting in the middle of the valley, touching the floor only slightly
+        let hits = |s: &Shape| p.to_shapes().unwrap().iter().any(|piece| piece.collision_with(s).is_some());
+        assert!(hits(&ball(3., 2.4)));
+        assert!(!hits(&ball(3., 3.)));
+        assert!(hits(&ball(1., 4.2)));
+    }
+
+    #[test]
+    fn shift_and_frame() {
+        let mut p = valley();
+        p.shift(&Vector(1., -1.));
+
+        assert_eq!(p.to_frame(), Frame { from: Vector(1., -1.), to: Vector(7., 3.) });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_validates() {
+        let v = valley();
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<SimplePolygon>(&json).unwrap(), v);
+
+        let bow_tie = "[[0,0],[2,2],[2,0],[0,2]]";
+        let error = serde_json::from_str::<SimplePolygon>(bow_tie).unwrap_err();
+        assert!(error.to_string().contains("edges intersect"));
+        assert!(serde_json::from_str::<SimplePolygon>("[[0,0],[1,1],[2,2]]").is_err());
+        assert!(serde_json::from_str::<SimplePolygon>(r#"{"vertices":[[0,0]]}"#).is_err());
+    }
+}
@@ -0,0 +1,577 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use vector::scalar::Scalar;
+use vector::vector::Vector;
+
+use crate::contact::Contact;
+use crate::frame::Frame;
+use crate::polygon::Polygon;
+use crate::ray::{Ray, RayHit};
+use crate::rectangle::Rectangle;
+use crate::segment::Segment;
+use crate::shape::Shape;
+use crate::traits::{Bounded, Translate};
+
+/**
+ * Kind of a tile. Slopes are named by the side their floor rises to.
+ * The shallow slopes rise half a tile per tile (the "22.5°" slopes of tile engines),
+ * so one ramp takes a low and a high tile.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Tile {
+    #[default]
+    Empty,
+    Solid,
+    // platform that is only solid from above
+    OneWay,
+    SlopeUpRight,
+    SlopeUpLeft,
+    ShallowUpRightLow,
+    ShallowUpRightHigh,
+    ShallowUpLeftLow,
+    ShallowUpLeftHigh,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileMapError {
+    // the tiles do not fill width * height
+    TileCount,
+    // the cell size is zero, negative or NaN
+    CellSize,
+}
+
+impl fmt::Display for TileMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            TileMapError::TileCount => write!(f, "tile map needs width * height tiles"),
+            TileMapError::CellSize => write!(f, "tile map cell size must be positive"),
+        };
+    }
+}
+
+/**
+ * Grid of square tiles, row 0 is at the bottom. Adjacent solid tiles are merged
+ * into larger rectangles for collisions, so shapes slide over the seams between them.
+ */
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// level files are checked against the grid size before any tile is looked up
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "TileMapData<T>",
+        into = "TileMapData<T>",
+        bound(serialize = "T: Scalar + serde::Serialize", deserialize = "T: Scalar + serde::Deserialize<'de>")
+    )
+)]
+pub struct TileMap<T = f32> {
+    // bottom left corner of the tile (0, 0)
+    pub origin: Vector<T>,
+    pub cell_size: T,
+    width: usize,
+    height: usize,
+    // row by row, from the bottom
+    tiles: Vec<Tile>,
+}
+
+impl<T: Scalar> TileMap<T> {
+    /**
+     * Map of empty tiles
+     */
+    pub fn new(origin: Vector<T>, cell_size: T, width: usize, height: usize) -> TileMap<T> {
+        return TileMap {
+            origin,
+            cell_size,
+            width,
+            height,
+            tiles: vec![Tile::Empty; width * height],
+        };
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /**
+     * Tiles outside of the map are empty
+     */
+    pub fn get(&self, x: usize, y: usize) -> Tile {
+        if x >= self.width || y >= self.height {
+            return Tile::Empty;
+        }
+        return self.tiles[y * self.width + x];
+    }
+
+    /**
+     * Tiles outside of the map are ignored
+     */
+    pub fn set(&mut self, x: usize, y: usize, tile: Tile) {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x] = tile;
+        }
+    }
+
+    pub fn to_frame(&self) -> Frame<T> {
+        return Frame {
+            from: self.origin,
+            to: self.corner(self.width, self.height),
+        };
+    }
+
+    pub fn shift(&mut self, offset: &Vector<T>) {
+        self.origin += *offset;
+    }
+
+    pub fn cell_frame(&self, x: usize, y: usize) -> Frame<T> {
+        return Frame {
+            from: self.corner(x, y),
+            to: self.corner(x + 1, y + 1),
+        };
+    }
+
+    /**
+     * Tile coordinates of the cell containing the point, None outside of the map
+     */
+    pub fn cell_at(&self, p: &Vector<T>) -> Option<(usize, usize)> {
+        let (x, y) = (self.column(p.0), self.row(p.1));
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return None;
+        }
+        return Some((x as usize, y as usize));
+    }
+
+    /**
+     * Solid part of the tile, None for an empty one. A one way platform is its whole cell.
+     */
+    pub fn tile_shape(&self, x: usize, y: usize) -> Option<Shape<T>> {
+        let f = self.cell_frame(x, y);
+        let half = f.from.1 + self.cell_size / T::from_f32(2.);
+        let (left, right, bottom, top) = (f.from.0, f.to.0, f.from.1, f.to.1);
+        let polygon = |points: &[Vector<T>]| Polygon::new(points).ok().map(Shape::Polygon);
+
+        return match self.get(x, y) {
+            Tile::Empty => None,
+            Tile::Solid | Tile::OneWay => Some(Shape::Rectangle(Rectangle::from_frame(&f))),
+            Tile::SlopeUpRight => polygon(&[f.from, Vector(right, bottom), f.to]),
+            Tile::SlopeUpLeft => polygon(&[f.from, Vector(right, bottom), Vector(left, top)]),
+            Tile::ShallowUpRightLow => polygon(&[f.from, Vector(right, bottom), Vector(right, half)]),
+            Tile::ShallowUpRightHigh => polygon(&[f.from, Vector(right, bottom), f.to, Vector(left, half)]),
+            Tile::ShallowUpLeftLow => polygon(&[f.from, Vector(right, bottom), Vector(left, half)]),
+            Tile::ShallowUpLeftHigh => polygon(&[f.from, Vector(right, bottom), Vector(right, half), Vector(left, top)]),
+        };
+    }
+
+    /**
+     * Non-empty tiles whose cells overlap the frame, as (x, y, tile)
+     */
+    pub fn tiles_in(&self, frame: &Frame<T>) -> impl Iterator<Item = (usize, usize, Tile)> + '_ {
+        let (x0, x1, y0, y1) = self.cell_range(frame);
+        return (y0..y1)
+            .flat_map(move |y| (x0..x1).map(move |x| (x, y, self.get(x, y))))
+            .filter(|(_, _, tile)| *tile != Tile::Empty);
+    }
+
+    /**
+     * Solid tiles merged into as few rectangles as the greedy scan finds
+     */
+    pub fn solid_rectangles(&self) -> Vec<Rectangle<T>> {
+        return self.merge_solids(0, self.width, 0, self.height);
+    }
+
+    /**
+     * First tile hit by the ray, with its coordinates.
+     * One way platforms are only hit on their top, by rays going down.
+     */
+    pub fn raycast(&self, ray: &Ray<T>) -> Option<(RayHit<T>, (usize, usize))> {
+        let start = self.to_frame().raycast(ray)?.distance;
+        let mut direction = ray.direction;
+        direction.normalize();
+        let entry = ray.origin + direction * start;
+        let mut x = self.column(entry.0).max(0).min(self.width as i64 - 1);
+        let mut y = self.row(entry.1).max(0).min(self.height as i64 - 1);
+
+        // distance along the ray to the next column and row boundary, None when parallel to them
+        let step_x: i64 = if direction.0 > T::ZERO { 1 } else { -1 };
+        let step_y: i64 = if direction.1 > T::ZERO { 1 } else { -1 };
+        let boundary = |cell: i64, step: i64, origin: T, o: T, d: T| -> Option<T> {
+            if d == T::ZERO {
+                return None;
+            }
+            let next = if step > 0 { cell + 1 } else { cell };
+            return Some((origin + self.cell_size * T::from_f32(next as f32) - o) / d);
+        };
+        let delta_x = (direction.0 != T::ZERO).then(|| self.cell_size / direction.0.abs());
+        let delta_y = (direction.1 != T::ZERO).then(|| self.cell_size / direction.1.abs());
+        let mut next_x = boundary(x, step_x, self.origin.0, ray.origin.0, direction.0);
+        let mut next_y = boundary(y, step_y, self.origin.1, ray.origin.1, direction.1);
+
+        while x >= 0 && y >= 0 && x < self.width as i64 && y < self.height as i64 {
+            let cell = (x as usize, y as usize);
+            if let Some(hit) = self.tile_raycast(cell.0, cell.1, ray) {
+                return Some((hit, cell));
+            }
+
+            let along_x = match (next_x, next_y) {
+                (Some(tx), Some(ty)) => tx < ty,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+            let t = if along_x { next_x } else { next_y };
+            if t.is_some_and(|t| ray.max_distance.is_some_and(|max| t > max)) {
+                return None;
+            }
+            if along_x {
+                x += step_x;
+                next_x = next_x.zip(delta_x).map(|(t, d)| t + d);
+            } else {
+                y += step_y;
+                next_y = next_y.zip(delta_y).map(|(t, d)| t + d);
+            }
+        }
+        return None;
+    }
+
+    /**
+     * Contacts of the shape with the map, the normals point from the tiles to the shape.
+     * The motion is how far the shape moved in the last step: a one way platform only
+     * stops shapes that were above it before the step.
+     */
+    pub fn contacts(&self, shape: &Shape<T>, motion: &Vector<T>) -> Vec<Contact<T>> {
+        let frame = shape.to_frame();
+        let (x0, x1, y0, y1) = self.cell_range(&frame);
+        let mut contacts = Vec::new();
+
+        for r in self.merge_solids(x0, x1, y0, y1) {
+            contacts.extend(Shape::Rectangle(r).contact_with(shape));
+        }
+
+        let previous_bottom = frame.from.1 - motion.1;
+        for (x, y, tile) in self.tiles_in(&frame) {
+            if tile == Tile::Solid {
+                continue;
+            }
+            let Some(tile_shape) = self.tile_shape(x, y) else {
+                continue;
+            };
+            let Some(contact) = tile_shape.contact_with(shape) else {
+                continue;
+            };
+            if tile == Tile::OneWay && (previous_bottom < self.cell_frame(x, y).to.1 || contact.normal.1 <= T::ZERO) {
+                continue;
+            }
+            contacts.push(contact);
+        }
+        return contacts;
+    }
+
+    fn tile_raycast(&self, x: usize, y: usize, ray: &Ray<T>) -> Option<RayHit<T>> {
+        if self.get(x, y) == Tile::OneWay {
+            let f = self.cell_frame(x, y);
+            if ray.direction.1 >= T::ZERO || ray.origin.1 < f.to.1 {
+                return None;
+            }
+            let top = Segment {
+                location: Vector(f.from.0, f.to.1),
+                vector: Vector(self.cell_size, T::ZERO),
+            };
+            return top.raycast(ray);
+        }
+        return self.tile_shape(x, y)?.raycast(ray);
+    }
+
+    /**
+     * Greedy meshing of the solid tiles in the window: each rectangle grows right
+     * as far as it can, then up while the whole row below it continues
+     */
+    fn merge_solids(&self, x0: usize, x1: usize, y0: usize, y1: usize) -> Vec<Rectangle<T>> {
+        let w = x1.saturating_sub(x0);
+        let h = y1.saturating_sub(y0);
+        let mut used = vec![false; w * h];
+        let free = |used: &[bool], i: usize, j: usize| !used[j * w + i] && self.get(x0 + i, y0 + j) == Tile::Solid;
+
+        let mut rectangles = Vec::new();
+        for j in 0..h {
+            for i in 0..w {
+                if !free(&used, i, j) {
+                    continue;
+                }
+                let mut width = 1;
+                while i + width < w && free(&used, i + width, j) {
+                    width += 1;
+                }
+                let mut height = 1;
+                while j + height < h && (i..i + width).all(|k| free(&used, k, j + height)) {
+                    height += 1;
+                }
+                for row in j..j + height {
+                    used[row * w + i..row * w + i + width].fill(true);
+                }
+                rectangles.push(Rectangle::from_frame(&Frame {
+                    from: self.corner(x0 + i, y0 + j),
+                    to: self.corner(x0 + i + width, y0 + j + height),
+                }));
+            }
+        }
+        return rectangles;
+    }
+
+    /**
+     * Columns x0..x1 and rows y0..y1 of the cells the frame overlaps, clamped to the map
+     */
+    fn cell_range(&self, frame: &Frame<T>) -> (usize, usize, usize, usize) {
+        let clamp = |v: i64, max: usize| v.max(0).min(max as i64) as usize;
+        return (
+            clamp(self.column(frame.from.0), self.width),
+            clamp(self.column(frame.to.0) + 1, self.width),
+            clamp(self.row(frame.from.1), self.height),
+            clamp(self.row(frame.to.1) + 1, self.height),
+        );
+    }
+
+    fn column(&self, x: T) -> i64 {
+        return ((x - self.origin.0) / self.cell_size).floor().to_f64() as i64;
+    }
+
+    fn row(&self, y: T) -> i64 {
+        return ((y - self.origin.1) / self.cell_size).floor().to_f64() as i64;
+    }
+
+    fn corner(&self, x: usize, y: usize) -> Vector<T> {
+        return self.origin + Vector(T::from_f32(x as f32), T::from_f32(y as f32)) * self.cell_size;
+    }
+}
+
+/**
+ * Tile map fields as they appear in a level file
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TileMapData<T> {
+    origin: Vector<T>,
+    cell_size: T,
+    width: usize,
+    height: usize,
+    tiles: Vec<Tile>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<TileMapData<T>> for TileMap<T> {
+    type Error = TileMapError;
+
+    fn try_from(data: TileMapData<T>) -> Result<TileMap<T>, TileMapError> {
+        if data.width.checked_mul(data.height) != Some(data.tiles.len()) {
+            return Err(TileMapError::TileCount);
+        }
+        if data.cell_size.partial_cmp(&T::ZERO) != Some(core::cmp::Ordering::Greater) {
+            return Err(TileMapError::CellSize);
+        }
+        return Ok(TileMap {
+            origin: data.origin,
+            cell_size: data.cell_size,
+            width: data.width,
+            height: data.height,
+            tiles: data.tiles,
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<TileMap<T>> for TileMapData<T> {
+    fn from(map: TileMap<T>) -> TileMapData<T> {
+        return TileMapData {
+            origin: map.origin,
+            cell_size: map.cell_size,
+            width: map.width,
+            height: map.height,
+            tiles: map.tiles,
+        };
+    }
+}
+
+impl<T: Scalar> Bounded<T> for TileMap<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return TileMap::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for TileMap<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        TileMap::shift(self, offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circle::Circle;
+    use vector::approx::Tolerance;
+    use vector::assert_approx_eq;
+
+    /**
+     * Map from rows of characters, the top row first
+     */
+    fn level(rows: &[&str]) -> TileMap {
+        let mut map = TileMap::new(Vector(0., 0.), 1., rows[0].len(), rows.len());
+        for (j, row) in rows.iter().rev().enumerate() {
+            for (i, c) in row.chars().enumerate() {
+                let tile = match c {
+                    '#' => Tile::Solid,
+                    '-' => Tile::OneWay,
+                    '/' => Tile::SlopeUpRight,
+                    '\\' => Tile::SlopeUpLeft,
+                    'l' => Tile::ShallowUpRightLow,
+                    'h' => Tile::ShallowUpRightHigh,
+                    _ => Tile::Empty,
+                };
+                map.set(i, j, tile);
+            }
+        }
+        return map;
+    }
+
+    fn player(x: f32, y: f32) -> Shape {
+        return Shape::Rectangle(Rectangle {
+            location: Vector(x, y),
+            dementions: Vector(0.8, 0.8),
+        });
+    }
+
+    #[test]
+    fn cells() {
+        let mut map = level(&["....", "#..#", "####"]);
+        map.shift(&Vector(10., 0.));
+
+        assert_eq!(map.to_frame(), Frame { from: Vector(10., 0.), to: Vector(14., 3.) });
+        assert_eq!(map.cell_at(&Vector(13.5, 1.5)), Some((3, 1)));
+        assert_eq!(map.cell_at(&Vector(9.5, 1.5)), None);
+        assert_eq!(map.get(3, 1), Tile::Solid);
+        assert_eq!(map.get(7, 1), Tile::Empty);
+        assert_eq!(map.cell_frame(1, 2), Frame { from: Vector(11., 2.), to: Vector(12., 3.) });
+
+        let found: Vec<(usize, usize)> = map
+            .tiles_in(&Frame { from: Vector(10.5, 0.5), to: Vector(11.5, 1.5) })
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert_eq!(found, vec![(0, 0), (1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn merged_rectangles() {
+        let map = level(&["##..", "##..", "####"]);
+        let rectangles = map.solid_rectangles();
+
+        assert_eq!(rectangles.len(), 2);
+        assert_eq!(rectangles[0].to_frame(), Frame { from: Vector(0., 0.), to: Vector(4., 1.) });
+        assert_eq!(rectangles[1].to_frame(), Frame { from: Vector(0., 1.), to: Vector(2., 3.) });
+    }
+
+    #[test]
+    fn slope_shapes() {
+        let map = level(&["/\\lh"]);
+        let areas: Vec<f32> = (0..4).map(|x| map.tile_shape(x, 0).unwrap().area()).collect();
+
+        assert_eq!(areas, vec![0.5, 0.5, 0.25, 0.75]);
+        assert_eq!(map.tile_shape(5, 0), None);
+    }
+
+    #[test]
+    fn raycast() {
+        let map = level(&["......", "..-...", "....#.", "#####/"]);
+
+        // straight down onto the floor
+        let (hit, cell) = map.raycast(&Ray::new(Vector(0.5, 3.5), Vector(0., -1.))).unwrap();
+        assert_eq!(cell, (0, 0));
+        assert_eq!(hit.point, Vector(0.5, 1.));
+        assert_eq!(hit.normal, Vector(0., 1.));
+
+        // from outside of the map, sideways into the block
+        let (hit, cell) = map.raycast(&Ray::new(Vector(-3., 1.5), Vector(1., 0.))).unwrap();
+        assert_eq!(cell, (4, 1));
+        assert_eq!(hit.distance, 7.);
+
+        // the slope faces up-left
+        let (hit, cell) = map.raycast(&Ray::new(Vector(5.8, 3.), Vector(0., -1.))).unwrap();
+        assert_eq!(cell, (5, 0));
+        assert_approx_eq!(hit.point, Vector(5.8, 0.8), Tolerance::Absolute(1e-5));
+        assert_approx_eq!(hit.normal, Vector(-0.70710677, 0.70710677));
+
+        assert_eq!(map.raycast(&Ray::new(Vector(0.5, 3.5), Vector(0., -1.)).with_max_distance(2.)), None);
+        assert_eq!(map.raycast(&Ray::new(Vector(0.5, 3.5), Vector(0., 1.))), None);
+        assert_eq!(map.raycast(&Ray::new(Vector(-1., 5.), Vector(1., 0.))), None);
+    }
+
+    #[test]
+    fn raycast_one_way() {
+        let map = level(&["......", "..-...", "......"]);
+
+        let (hit, cell) = map.raycast(&Ray::new(Vector(2.5, 2.8), Vector(0., -1.))).unwrap();
+        assert_eq!(cell, (2, 1));
+        assert_eq!(hit.point, Vector(2.5, 2.));
+        assert_eq!(map.raycast(&Ray::new(Vector(2.5, 0.5), Vector(0., 1.))), None);
+    }
+
+    #[test]
+    fn sliding_over_seams() {
+        let map = level(&["......", "######"]);
+
+        // sunk a little into the floor right over the seam between two tiles
+        let contacts = map.contacts(&player(2., 1.35), &Vector(0.5, -0.1));
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].normal, Vector(0., 1.));
+        assert_approx_eq!(contacts[0].depth, 0.05);
+        assert_eq!(contacts[0].point_count, 2);
+
+        assert!(map.contacts(&player(2., 1.5), &Vector(0., 0.)).is_empty());
+    }
+
+    #[test]
+    fn one_way_platform() {
+        let map = level(&["......", "..-...", "......"]);
+
+        // landing from above
+        let contacts = map.contacts(&player(2.5, 2.35), &Vector(0., -0.2));
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].normal, Vector(0., 1.));
+
+        // jumping through from below
+        assert!(map.contacts(&player(2.5, 1.9), &Vector(0., 0.3)).is_empty());
+    }
+
+    #[test]
+    fn standing_on_slope() {
+        let map = level(&["....", "./..", "####"]);
+
+        let ball = Shape::Circle(Circle {
+            location: Vector(1.5, 1.9),
+            radius: 0.3,
+        });
+        let contacts = map.contacts(&ball, &Vector(0., -0.1));
+        assert_eq!(contacts.len(), 1);
+        assert_approx_eq!(contacts[0].normal, Vector(-0.70710677, 0.70710677));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_validates() {
+        let map = level(&["#-", "/#"]);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(serde_json::from_str::<TileMap>(&json).unwrap(), map);
+
+        let load = |json: &str| serde_json::from_str::<TileMap>(json).map_err(|e| e.to_string());
+        let error = load(r#"{"origin":[0,0],"cell_size":1,"width":3,"height":2,"tiles":["solid"]}"#);
+        assert!(error.unwrap_err().contains("width * height tiles"));
+        let error = load(r#"{"origin":[0,0],"cell_size":0,"width":1,"height":1,"tiles":["solid"]}"#);
+        assert!(error.unwrap_err().contains("cell size must be positive"));
+        assert!(load(r#"{"origin":[0,0],"cell_size":-1,"width":0,"height":0,"tiles":[]}"#).is_err());
+        assert!(load(r#"{"origin":[0,0],"cell_size":2,"width":0,"height":5,"tiles":[]}"#).is_ok());
+    }
+}
@@ -0,0 +1,365 @@
+/*!
+ * Traits for the common shape queries, so code can work with any shape type,
+ * including ones defined outside of this crate, instead of the closed Shape enum.
+ */
+
+use vector::scalar::Scalar;
+use vector::vector::Vector;
+
+use crate::capsule::Capsule;
+use crate::circle::Circle;
+use crate::frame::Frame;
+use crate::oriented_rectangle::OrientedRectangle;
+use crate::polygon::Polygon;
+use crate::rectangle::Rectangle;
+use crate::segment::Segment;
+use crate::shape::Shape;
+
+pub trait Bounded<T: Scalar> {
+    /**
+     * Axis-aligned frame containing the whole shape
+     */
+    fn to_frame(&self) -> Frame<T>;
+}
+
+pub trait Area<T: Scalar> {
+    fn area(&self) -> T;
+}
+
+pub trait Translate<T: Scalar> {
+    fn shift(&mut self, offset: &Vector<T>);
+}
+
+pub trait PointQuery<T: Scalar> {
+    /**
+     * Point of the shape nearest to p, p itself if it is inside
+     */
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T>;
+
+    /**
+     * Distance to the surface, negative inside
+     */
+    fn signed_distance(&self, p: &Vector<T>) -> T;
+
+    fn distance_to_point(&self, p: &Vector<T>) -> T {
+        return self.signed_distance(p).max(T::ZERO);
+    }
+
+    /**
+     * True if the point is strictly inside, shapes without area contain no points
+     */
+    fn contains_point(&self, p: &Vector<T>) -> bool {
+        return self.signed_distance(p) < T::ZERO;
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Circle<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Circle::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Circle<T> {
+    fn area(&self) -> T {
+        return Circle::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Circle<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        self.location += *offset;
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Circle<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Circle::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Circle::signed_distance(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Rectangle<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Rectangle::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Rectangle<T> {
+    fn area(&self) -> T {
+        return Rectangle::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Rectangle<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Rectangle::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Rectangle<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Rectangle::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Rectangle::signed_distance(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Segment<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Segment::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Segment<T> {
+    fn area(&self) -> T {
+        return T::ZERO;
+    }
+}
+
+impl<T: Scalar> Translate<T> for Segment<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Segment::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Segment<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Segment::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Segment::signed_distance(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Frame<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return *self;
+    }
+}
+
+impl<T: Scalar> Area<T> for Frame<T> {
+    fn area(&self) -> T {
+        return Frame::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Frame<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        self.from += *offset;
+        self.to += *offset;
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Frame<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Frame::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Frame::signed_distance(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Polygon<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Polygon::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Polygon<T> {
+    fn area(&self) -> T {
+        return Polygon::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Polygon<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Polygon::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Polygon<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Polygon::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Polygon::signed_distance(self, p);
+    }
+
+    fn contains_point(&self, p: &Vector<T>) -> bool {
+        return Polygon::contains_point(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Capsule<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Capsule::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Capsule<T> {
+    fn area(&self) -> T {
+        return Capsule::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Capsule<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Capsule::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Capsule<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Capsule::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Capsule::signed_distance(self, p);
+    }
+
+    fn contains_point(&self, p: &Vector<T>) -> bool {
+        return Capsule::contains_point(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for OrientedRectangle<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return OrientedRectangle::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for OrientedRectangle<T> {
+    fn area(&self) -> T {
+        return OrientedRectangle::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for OrientedRectangle<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        OrientedRectangle::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for OrientedRectangle<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return OrientedRectangle::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return OrientedRectangle::signed_distance(self, p);
+    }
+
+    fn contains_point(&self, p: &Vector<T>) -> bool {
+        return OrientedRectangle::contains_point(self, p);
+    }
+}
+
+impl<T: Scalar> Bounded<T> for Shape<T> {
+    fn to_frame(&self) -> Frame<T> {
+        return Shape::to_frame(self);
+    }
+}
+
+impl<T: Scalar> Area<T> for Shape<T> {
+    fn area(&self) -> T {
+        return Shape::area(self);
+    }
+}
+
+impl<T: Scalar> Translate<T> for Shape<T> {
+    fn shift(&mut self, offset: &Vector<T>) {
+        Shape::shift(self, offset);
+    }
+}
+
+impl<T: Scalar> PointQuery<T> for Shape<T> {
+    fn closest_point(&self, p: &Vector<T>) -> Vector<T> {
+        return Shape::closest_point(self, p);
+    }
+
+    fn signed_distance(&self, p: &Vector<T>) -> T {
+        return Shape::signed_distance(self, p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a shape from outside of the crate only has to implement the traits
+    struct Ring {
+        center: Vector,
+        inner: f32,
+        outer: f32,
+    }
+
+    impl Bounded<f32> for Ring {
+        fn to_frame(&self) -> Frame {
+            let r = Vector(self.outer, self.outer);
+            return Frame { from: self.center - r, to: self.center + r };
+        }
+    }
+
+    impl PointQuery<f32> for Ring {
+        fn closest_point(&self, p: &Vector) -> Vector {
+            let d = *p - self.center;
+            let distance = d.size();
+            let radius = distance.max(self.inner).min(self.outer);
+            return self.center + d * (radius / distance);
+        }
+
+        fn signed_distance(&self, p: &Vector) -> f32 {
+            let distance = p.distance(&self.center);
+            return (distance - self.outer).max(self.inner - distance);
+        }
+    }
+
+    fn nearest<'a>(shapes: &'a [&'a dyn PointQuery<f32>], p: &Vector) -> &'a dyn PointQuery<f32> {
+        return *shapes
+            .iter()
+            .min_by(|a, b| a.distance_to_point(p).total_cmp(&b.distance_to_point(p)))
+            .unwrap();
+    }
+
+    #[test]
+    fn trait_objects() {
+        let ring = Ring { center: Vector(0., 0.), inner: 1., outer: 2. };
+        let circle = Circle { location: Vector(5., 0.), radius: 1. };
+        let segment = Segment { location: Vector(0., 5.), vector: Vector(2., 0.) };
+        let shapes: [&dyn PointQuery<f32>; 3] = [&ring, &circle, &segment];
+
+        assert_eq!(nearest(&shapes, &Vector(3.5, 0.)).closest_point(&Vector(3.5, 0.)), Vector(4., 0.));
+        assert_eq!(nearest(&shapes, &Vector(1., 4.)).distance_to_point(&Vector(1., 4.)), 1.);
+        assert!(ring.contains_point(&Vector(1.5, 0.)));
+        assert!(!ring.contains_point(&Vector(0., 0.)));
+        assert!(!segment.contains_point(&Vector(1., 5.)));
+    }
+
+    fn total_area<T: Scalar>(shapes: &[&dyn Area<T>]) -> T {
+        return shapes.iter().fold(T::ZERO, |sum, s| sum + s.area());
+    }
+
+    #[test]
+    fn generic_code() {
+        let mut frame = Frame { from: Vector(0., 0.), to: Vector(2., 3.) };
+        let rectangle = Rectangle { location: Vector(0., 0.), dementions: Vector(1., 2.) };
+        let segment = Segment { location: Vector(0., 0.), vector: Vector(1., 0.) };
+        assert_eq!(total_area::<f32>(&[&frame, &rectangle, &segment]), 8.);
+
+        Translate::shift(&mut frame, &Vector(1., 1.));
+        assert_eq!(Bounded::to_frame(&frame), Frame { from: Vector(1., 1.), to: Vector(3., 4.) });
+
+        let mut shape = Shape::Circle(Circle { location: Vector(0., 0.), radius: 1. });
+        Translate::shift(&mut shape, &Vector(1., 0.));
+        assert!(PointQuery::contains_point(&shape, &Vector(1.5, 0.)));
+        assert!(!PointQuery::contains_point(&Shape::Dot(Vector(0., 0.)), &Vector(0., 0.)));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::fixed::{Fix16, Fix32};
+use crate::polar::{Angle, Polar};
+use crate::scalar::Scalar;
+use crate::transform::Transform2D;
+use crate::vector::Vector;
+
+/**
+ * How far apart two values may be to still count as equal
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tolerance<T> {
+    // |a - b| <= epsilon
+    Absolute(T),
+    // |a - b| <= epsilon or |a - b| <= max(|a|, |b|) * max_relative
+    Relative { epsilon: T, max_relative: T },
+    // |a - b| <= epsilon or a and b are at most max_ulps representable values apart
+    Ulps { epsilon: T, max_ulps: u64 },
+}
+
+impl<T: Scalar> Default for Tolerance<T> {
+    fn default() -> Self {
+        return Tolerance::Relative {
+            epsilon: T::EPSILON,
+            max_relative: T::EPSILON,
+        };
+    }
+}
+
+/**
+ * Approximate equality, compares every component of a geometry type
+ * with the same tolerance
+ */
+pub trait ApproxEq {
+    type Scalar: Scalar;
+
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance<Self::Scalar>) -> bool;
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Scalar) -> bool {
+        return self.approx_eq(other, Tolerance::Absolute(epsilon));
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Scalar, max_relative: Self::Scalar) -> bool {
+        return self.approx_eq(other, Tolerance::Relative { epsilon, max_relative });
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Scalar, max_ulps: u64) -> bool {
+        return self.approx_eq(other, Tolerance::Ulps { epsilon, max_ulps });
+    }
+}
+
+macro_rules! impl_scalar_approx_eq {
+    ($t:ty) => {
+        impl ApproxEq for $t {
+            type Scalar = $t;
+
+            fn approx_eq(&self, other: &$t, tolerance: Tolerance<$t>) -> bool {
+                let diff = Scalar::abs(*self - *other);
+                return match tolerance {
+                    Tolerance::Absolute(epsilon) => diff <= epsilon,
+                    Tolerance::Relative { epsilon, max_relative } => {
+                        diff <= epsilon || diff <= Scalar::max(Scalar::abs(*self), Scalar::abs(*other)) * max_relative
+                    }
+                    Tolerance::Ulps { epsilon, max_ulps } => {
+                        diff <= epsilon || self.ulps_between(*other) <= max_ulps
+                    }
+                };
+            }
+        }
+    };
+}
+
+impl_scalar_approx_eq!(f32);
+impl_scalar_approx_eq!(f64);
+impl_scalar_approx_eq!(Fix16);
+impl_scalar_approx_eq!(Fix32);
+
+impl<A: ApproxEq> ApproxEq for Option<A> {
+    type Scalar = A::Scalar;
+
+    fn approx_eq(&self, other: &Option<A>, tolerance: Tolerance<A::Scalar>) -> bool {
+        return match (self, other) {
+            (Some(a), Some(b)) => a.approx_eq(b, tolerance),
+            (None, None) => true,
+            _ => false,
+        };
+    }
+}
+
+impl<T> ApproxEq for Vector<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Vector<T>, tolerance: Tolerance<T>) -> bool {
+        return self.0.approx_eq(&other.0, tolerance) && self.1.approx_eq(&other.1, tolerance);
+    }
+}
+
+impl<T> ApproxEq for Angle<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Angle<T>, tolerance: Tolerance<T>) -> bool {
+        return self.0.approx_eq(&other.0, tolerance);
+    }
+}
+
+impl<T> ApproxEq for Polar<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Polar<T>, tolerance: Tolerance<T>) -> bool {
+        return self.length.approx_eq(&other.length, tolerance)
+            && self.rotation.approx_eq(&other.rotation, tolerance);
+    }
+}
+
+impl<T> ApproxEq for Transform2D<T>
+where
+    T: Scalar + ApproxEq<Scalar = T>,
+{
+    type Scalar = T;
+
+    fn approx_eq(&self, other: &Transform2D<T>, tolerance: Tolerance<T>) -> bool {
+        return self.x_axis.approx_eq(&other.x_axis, tolerance)
+            && self.y_axis.approx_eq(&other.y_axis, tolerance)
+            && self.translation.approx_eq(&other.translation, tolerance);
+    }
+}
+
+/**
+ * Asserts that two values are approximately equal, e.g.
+ * `assert_approx_eq!(a, b)` with the default tolerance (relative, machine epsilon)
+ * or `assert_approx_eq!(a, b, Tolerance::Absolute(1e-5))`.
+ */
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, $crate::approx::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let tolerance = $tolerance;
+                if !$crate::approx::ApproxEq::approx_eq(left, right, tolerance) {
+                    panic!(
+                        "assertion `left ≈ right` failed\n      left: {:?}\n     right: {:?}\n tolerance: {:?}",
+                        left, right, tolerance
+                    );
+                }
+            }
+        }
+    };
+}
+
+/**
+ * Asserts that two values are not approximately equal
+ */
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_ne!($left, $right, $crate::approx::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let tolerance = $tolerance;
+                if $crate::approx::ApproxEq::approx_eq(left, right, tolerance) {
+                    panic!(
+                        "assertion `left ≉ right` failed\n      left: {:?}\n     right: {:?}\n tolerance: {:?}",
+                        left, right, tolerance
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        assert!(1f32.abs_diff_eq(&1.05, 0.1));
+        assert!(!1f32.abs_diff_eq(&1.2, 0.1));
+
+        assert!(1000f64.relative_eq(&1001., 0., 1e-3));
+        assert!(!1f64.relative_eq(&1.01, 0., 1e-3));
+
+        assert!(1f32.ulps_eq(&(1. + 2. * f32::EPSILON), 0., 2));
+        assert!(!1f32.ulps_eq(&(1. + 3. * f32::EPSILON), 0., 2));
+
+        assert!(Fix16::from_raw(10).ulps_eq(&Fix16::from_raw(12), Fix16::ZERO, 2));
+    }
+
+    #[test]
+    fn default_tolerance() {
+        assert!((0.1f32 + 0.2).approx_eq(&0.3, Tolerance::default()));
+        assert!(!(0.1f32).approx_eq(&0.1001, Tolerance::default()));
+    }
+
+    #[test]
+    fn geometry() {
+        let a = Vector(0.1f64 + 0.2, 1.);
+        let b = Vector(0.3, 1.);
+
+        assert_ne!(a, b);
+        assert_approx_eq!(a, b);
+        assert_approx_ne!(a, Vector(0.3, 1.1));
+        assert_approx_eq!(a, Vector(0.3, 1.1), Tolerance::Absolute(0.2));
+
+        let p = Polar{ length: 1f32, rotation: Angle(0.5) };
+        assert_approx_eq!(p, Polar{ length: 1., rotation: Angle(0.5 + 1e-8) });
+    }
+
+    #[test]
+    fn options() {
+        assert_approx_eq!(Some(Vector(1f32, 2.)), Some(Vector(1., 2. + 1e-7)));
+        assert_approx_eq!(None::<Vector>, None);
+        assert_approx_ne!(Some(Vector(1f32, 2.)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "left ≈ right")]
+    fn assert_fails() {
+        assert_approx_eq!(1f32, 2f32);
+    }
+}
@@ -1,2 +1,12 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod vector;
 pub mod polar;
+pub mod scalar;
+pub mod fixed;
+pub mod predicates;
+pub mod transform;
+pub mod approx;
+mod math;
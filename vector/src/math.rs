@@ -0,0 +1,102 @@
+/*!
+ * Float math backend used by the Scalar impls of f32 and f64.
+ *
+ * With the `std` feature the inherent float methods of the standard library
+ * are used. With the `libm` feature the functions come from the libm crate,
+ * which works without std and gives the same results on every platform;
+ * libm wins when both features are enabled.
+ */
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("vector needs a math backend, enable the `std` or the `libm` feature");
+
+pub(crate) trait FloatMath: Sized {
+    fn sqrt(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn floor(self) -> Self;
+    fn abs(self) -> Self;
+}
+
+#[cfg(feature = "libm")]
+macro_rules! impl_float_math {
+    ($t:ident, $sqrt:ident, $atan2:ident, $sin:ident, $cos:ident, $floor:ident, $abs:ident) => {
+        impl FloatMath for $t {
+            fn sqrt(self) -> Self {
+                return libm::$sqrt(self);
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                return libm::$atan2(self, x);
+            }
+
+            fn sin(self) -> Self {
+                return libm::$sin(self);
+            }
+
+            fn cos(self) -> Self {
+                return libm::$cos(self);
+            }
+
+            fn floor(self) -> Self {
+                return libm::$floor(self);
+            }
+
+            fn abs(self) -> Self {
+                return libm::$abs(self);
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "std", not(feature = "libm")))]
+macro_rules! impl_float_math {
+    ($t:ident, $($_f:ident),*) => {
+        impl FloatMath for $t {
+            fn sqrt(self) -> Self {
+                return $t::sqrt(self);
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                return $t::atan2(self, x);
+            }
+
+            fn sin(self) -> Self {
+                return $t::sin(self);
+            }
+
+            fn cos(self) -> Self {
+                return $t::cos(self);
+            }
+
+            fn floor(self) -> Self {
+                return $t::floor(self);
+            }
+
+            fn abs(self) -> Self {
+                return $t::abs(self);
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_float_math!(f32, sqrtf, atan2f, sinf, cosf, floorf, fabsf);
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_float_math!(f64, sqrt, atan2, sin, cos, floor, fabs);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backend() {
+        assert_eq!(FloatMath::sqrt(9f32), 3.);
+        assert_eq!(FloatMath::atan2(1f64, 0.), core::f64::consts::FRAC_PI_2);
+        assert_eq!(FloatMath::sin(0f32), 0.);
+        assert_eq!(FloatMath::cos(0f64), 1.);
+        assert_eq!(FloatMath::floor(-1.5f32), -2.);
+        assert_eq!(FloatMath::abs(-2f64), 2.);
+    }
+}
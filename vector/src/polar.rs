@@ -1,34 +1,306 @@
+use core::ops;
+
+use crate::scalar::Scalar;
 use crate::vector::Vector;
 
+/**
+ * Angle in radians
+ */
+#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Angle<T = f32>(pub T);
+
+impl<T: Scalar> Angle<T> {
+    pub fn from_radians(r: T) -> Angle<T> {
+        return Angle(r);
+    }
+
+    pub fn from_degrees(d: T) -> Angle<T> {
+        return Angle(d * T::PI / T::from_f32(180.));
+    }
+
+    pub fn radians(&self) -> T {
+        return self.0;
+    }
+
+    pub fn degrees(&self) -> T {
+        return self.0 * T::from_f32(180.) / T::PI;
+    }
+
+    pub fn sin(&self) -> T {
+        return self.0.sin();
+    }
+
+    pub fn cos(&self) -> T {
+        return self.0.cos();
+    }
+
+    /**
+     * Same direction, wrapped into (-PI, PI]
+     */
+    pub fn normalize(&self) -> Angle<T> {
+        let two_pi = T::PI + T::PI;
+        let turns = -((T::PI - self.0) / two_pi).floor();
+        return Angle(self.0 - two_pi * turns);
+    }
+
+    /**
+     * Shortest signed rotation from self to a, in (-PI, PI]
+     */
+    pub fn difference(&self, a: &Angle<T>) -> Angle<T> {
+        return (*a - *self).normalize();
+    }
+
+    /**
+     * Interpolates along the shorter arc, the result is normalized
+     */
+    pub fn lerp(&self, a: &Angle<T>, t: T) -> Angle<T> {
+        return (*self + self.difference(a) * t).normalize();
+    }
+}
+
+impl<T: Scalar> ops::Add for Angle<T> {
+    type Output = Angle<T>;
+
+    fn add(self, a: Angle<T>) -> Angle<T> {
+        return Angle(self.0 + a.0);
+    }
+}
+
+impl<T: Scalar> ops::Sub for Angle<T> {
+    type Output = Angle<T>;
+
+    fn sub(self, a: Angle<T>) -> Angle<T> {
+        return Angle(self.0 - a.0);
+    }
+}
+
+impl<T: Scalar> ops::Neg for Angle<T> {
+    type Output = Angle<T>;
+
+    fn neg(self) -> Angle<T> {
+        return Angle(-self.0);
+    }
+}
+
+impl<T: Scalar> ops::Mul<T> for Angle<T> {
+    type Output = Angle<T>;
+
+    fn mul(self, s: T) -> Angle<T> {
+        return Angle(self.0 * s);
+    }
+}
+
+impl<T: Scalar> ops::Div<T> for Angle<T> {
+    type Output = Angle<T>;
+
+    fn div(self, s: T) -> Angle<T> {
+        return Angle(self.0 / s);
+    }
+}
+
 /**
  * Polar representation of vector
  */
-#[derive(Default, Clone, Debug)]
-pub struct Polar {
-    pub length: f32,
-    pub rotation: f32,
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polar<T = f32> {
+    pub length: T,
+    pub rotation: Angle<T>,
 }
 
-impl Polar {
-    pub fn as_vector (&self) -> Vector {
+impl<T: Scalar> Polar<T> {
+    pub fn as_vector (&self) -> Vector<T> {
         let x = self.length * self.rotation.cos();
         let y = self.length * self.rotation.sin();
         return Vector(x,y);
     }
+
+    /**
+     * Counter-clockwise rotation, the rotation of the result is normalized
+     */
+    pub fn rotate(&self, a: Angle<T>) -> Polar<T> {
+        return Polar {
+            length: self.length,
+            rotation: (self.rotation + a).normalize(),
+        };
+    }
+
+    /**
+     * Multiplies the length, a negative factor turns the vector around
+     */
+    pub fn scale(&self, s: T) -> Polar<T> {
+        if s < T::ZERO {
+            return Polar {
+                length: -self.length * s,
+                rotation: (self.rotation + Angle(T::PI)).normalize(),
+            };
+        }
+        return Polar {
+            length: self.length * s,
+            rotation: self.rotation,
+        };
+    }
+}
+
+/**
+ * Product as complex numbers: lengths are multiplied, rotations are added
+ */
+impl<T: Scalar> ops::Mul for Polar<T> {
+    type Output = Polar<T>;
+
+    fn mul(self, p: Polar<T>) -> Polar<T> {
+        return Polar {
+            length: self.length * p.length,
+            rotation: (self.rotation + p.rotation).normalize(),
+        };
+    }
+}
+
+/**
+ * Quotient as complex numbers: lengths are divided, rotations are subtracted
+ */
+impl<T: Scalar> ops::Div for Polar<T> {
+    type Output = Polar<T>;
+
+    fn div(self, p: Polar<T>) -> Polar<T> {
+        return Polar {
+            length: self.length / p.length,
+            rotation: (self.rotation - p.rotation).normalize(),
+        };
+    }
+}
+
+impl<T: Scalar> From<Vector<T>> for Polar<T> {
+    fn from(v: Vector<T>) -> Polar<T> {
+        let mut v = v;
+        return v.as_polar();
+    }
+}
+
+impl<T: Scalar> From<Polar<T>> for Vector<T> {
+    fn from(p: Polar<T>) -> Vector<T> {
+        return p.as_vector();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixed::Fix16;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_near(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4 * b.abs().max(1.), "{} != {}", a, b);
+    }
 
     #[test]
     fn as_vector() {
 
-        let p = Polar{length: 1., rotation: 0.};
+        let p = Polar{length: 1., rotation: Angle(0.)};
         let v = p.as_vector();
 
         assert_eq!(v.0, 1.);
         assert_eq!(v.1, 0.);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn as_vector_fixed() {
+        let p = Polar{length: Fix16::from_int(2), rotation: Angle(Fix16::PI / Fix16::from_int(2))};
+        let v = p.as_vector();
+
+        assert!(v.0.abs() < Fix16::from_f64(1e-3));
+        assert!((v.1 - Fix16::from_int(2)).abs() < Fix16::from_f64(1e-3));
+    }
+
+    #[test]
+    fn angle_units() {
+        assert_near(Angle::from_degrees(90.).radians(), FRAC_PI_2);
+        assert_near(Angle::from_radians(PI).degrees(), 180.);
+    }
+
+    #[test]
+    fn angle_normalize() {
+        assert_near(Angle(3. * PI).normalize().0, PI);
+        assert_near(Angle(-PI).normalize().0, PI);
+        assert_near(Angle(PI).normalize().0, PI);
+        assert_near(Angle(-3. * FRAC_PI_2).normalize().0, FRAC_PI_2);
+        assert_near(Angle(0.5).normalize().0, 0.5);
+        assert_near(Angle(0.5 + 4. * PI).normalize().0, 0.5);
+    }
+
+    #[test]
+    fn angle_difference() {
+        let a = Angle::from_degrees(170.);
+        let b = Angle::from_degrees(-170.);
+
+        assert_near(a.difference(&b).degrees(), 20.);
+        assert_near(b.difference(&a).degrees(), -20.);
+        assert_near(a.difference(&a).degrees(), 0.);
+    }
+
+    #[test]
+    fn angle_lerp() {
+        let a = Angle::from_degrees(170.);
+        let b = Angle::from_degrees(-170.);
+
+        assert_near(a.lerp(&b, 0.5).degrees(), 180.);
+        assert_near(a.lerp(&b, 0.75).degrees(), -175.);
+        assert_near(a.lerp(&b, 0.).degrees(), 170.);
+    }
+
+    #[test]
+    fn rotate_and_scale() {
+        let p = Polar{length: 2., rotation: Angle::from_degrees(170.)};
+
+        let r = p.rotate(Angle::from_degrees(20.));
+        assert_near(r.length, 2.);
+        assert_near(r.rotation.degrees(), -170.);
+
+        let s = p.scale(-1.5);
+        assert_near(s.length, 3.);
+        assert_near(s.rotation.degrees(), -10.);
+        assert_near(p.scale(2.).length, 4.);
+    }
+
+    #[test]
+    fn complex_arithmetic() {
+        let a = Polar{length: 2., rotation: Angle::from_degrees(30.)};
+        let b = Polar{length: 4., rotation: Angle::from_degrees(60.)};
+
+        let m = a * b;
+        assert_near(m.length, 8.);
+        assert_near(m.rotation.degrees(), 90.);
+
+        let d = a / b;
+        assert_near(d.length, 0.5);
+        assert_near(d.rotation.degrees(), -30.);
+
+        // same as complex multiplication (1 + i) * (1 + i) = 2i
+        let v = Polar::from(Vector(1., 1.));
+        assert!((Vector::from(v * v) - Vector(0., 2.)).size() < 1e-5);
+    }
+
+    #[test]
+    fn round_trip() {
+        for i in -8..8 {
+            let v = Vector(i as f32 * 0.7, 3. - i as f32);
+            let back = Vector::from(Polar::from(v));
+            assert!((back - v).size() < 1e-5);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let p = Polar{length: 2., rotation: Angle(0.5)};
+
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, r#"{"length":2.0,"rotation":0.5}"#);
+        assert_eq!(serde_json::from_str::<Polar>(&json).unwrap(), p);
+
+        let ron = ron::to_string(&p).unwrap();
+        assert_eq!(ron::from_str::<Polar>(&ron).unwrap(), p);
+    }
+}
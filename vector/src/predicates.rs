@@ -0,0 +1,282 @@
+/*!
+ * Robust geometric predicates (after J. R. Shewchuk, "Adaptive Precision
+ * Floating-Point Arithmetic and Fast Robust Geometric Predicates").
+ *
+ * Each predicate first evaluates the determinant with ordinary floats and
+ * checks it against an error bound. Only when the result is too close to
+ * zero to trust, it is recomputed exactly with floating-point expansions,
+ * so the sign of the result is always correct.
+ */
+
+use alloc::vec::Vec;
+
+// 2^-53, half of the f64 machine epsilon
+const EPSILON: f64 = f64::EPSILON * 0.5;
+// 2^27 + 1, used to split a f64 into two non-overlapping halves
+const SPLITTER: f64 = 134_217_729.;
+
+const CCW_ERRBOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ICC_ERRBOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/**
+ * Orientation of the triangle (a, b, c).
+ * Positive if c is to the left of the directed line a -> b (counter-clockwise),
+ * negative if it is to the right (clockwise), zero if the points are collinear.
+ * The value is twice the signed area of the triangle.
+ */
+pub fn orient2d(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let detleft = (a[0] - c[0]) * (b[1] - c[1]);
+    let detright = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = detleft - detright;
+
+    let detsum = detleft.abs() + detright.abs();
+    if det.abs() >= CCW_ERRBOUND * detsum {
+        return det;
+    }
+
+    let acx = two_diff(a[0], c[0]);
+    let bcy = two_diff(b[1], c[1]);
+    let acy = two_diff(a[1], c[1]);
+    let bcx = two_diff(b[0], c[0]);
+
+    let left = expansion_product(&acx, &bcy);
+    let right = expansion_product(&acy, &bcx);
+    return estimate(&expansion_diff(&left, &right));
+}
+
+/**
+ * Position of d relative to the circle through a, b and c.
+ * For counter-clockwise a, b, c the result is positive if d lies inside
+ * the circle, negative if outside and zero if all four points are cocircular.
+ * The sign is reversed for clockwise a, b, c.
+ */
+pub fn incircle(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> f64 {
+    let adx = a[0] - d[0];
+    let bdx = b[0] - d[0];
+    let cdx = c[0] - d[0];
+    let ady = a[1] - d[1];
+    let bdy = b[1] - d[1];
+    let cdy = c[1] - d[1];
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > ICC_ERRBOUND * permanent {
+        return det;
+    }
+
+    let adx = two_diff(a[0], d[0]);
+    let bdx = two_diff(b[0], d[0]);
+    let cdx = two_diff(c[0], d[0]);
+    let ady = two_diff(a[1], d[1]);
+    let bdy = two_diff(b[1], d[1]);
+    let cdy = two_diff(c[1], d[1]);
+
+    let lift = |x: &[f64], y: &[f64]| expansion_sum(&expansion_product(x, x), &expansion_product(y, y));
+    let cross = |x1: &[f64], y1: &[f64], x2: &[f64], y2: &[f64]| {
+        expansion_diff(&expansion_product(x1, y2), &expansion_product(y1, x2))
+    };
+
+    let aterm = expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let bterm = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let cterm = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+
+    return estimate(&expansion_sum(&expansion_sum(&aterm, &bterm), &cterm));
+}
+
+/**
+ * a + b = x + y exactly, where x is the rounded sum
+ */
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let bvirt = x - a;
+    let avirt = x - bvirt;
+    let y = (a - avirt) + (b - bvirt);
+    return (x, y);
+}
+
+/**
+ * a - b as an expansion
+ */
+fn two_diff(a: f64, b: f64) -> [f64; 2] {
+    let x = a - b;
+    let bvirt = a - x;
+    let avirt = x + bvirt;
+    let y = (a - avirt) + (bvirt - b);
+    return [y, x];
+}
+
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let big = c - a;
+    let hi = c - big;
+    return (hi, a - hi);
+}
+
+/**
+ * a * b = x + y exactly, where x is the rounded product
+ */
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    let (ahi, alo) = split(a);
+    let (bhi, blo) = split(b);
+    let err1 = x - ahi * bhi;
+    let err2 = err1 - alo * bhi;
+    let err3 = err2 - ahi * blo;
+    let y = alo * blo - err3;
+    return (x, y);
+}
+
+/**
+ * Sum of two expansions, components are kept in increasing magnitude
+ */
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = e.to_vec();
+    for &b in f {
+        let mut q = b;
+        for c in h.iter_mut() {
+            let (x, y) = two_sum(q, *c);
+            *c = y;
+            q = x;
+        }
+        h.push(q);
+    }
+    return compress(h);
+}
+
+fn expansion_diff(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let negated: Vec<f64> = f.iter().map(|v| -v).collect();
+    return expansion_sum(e, &negated);
+}
+
+fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h = Vec::with_capacity(e.len() * 2);
+    let mut q = 0.;
+    for &c in e {
+        let (p1, p0) = two_product(c, b);
+        let (s, l) = two_sum(q, p0);
+        h.push(l);
+        let (x, y) = two_sum(p1, s);
+        h.push(y);
+        q = x;
+    }
+    h.push(q);
+    return compress(h);
+}
+
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    let mut h = Vec::new();
+    for &b in f {
+        h = expansion_sum(&h, &scale_expansion(e, b));
+    }
+    return h;
+}
+
+/**
+ * Drops zero components to keep the expansions short
+ */
+fn compress(mut h: Vec<f64>) -> Vec<f64> {
+    h.retain(|v| *v != 0.);
+    return h;
+}
+
+/**
+ * Approximate value of an expansion. Components are non-overlapping,
+ * so the sign of the result is the exact sign of the expansion.
+ */
+fn estimate(e: &[f64]) -> f64 {
+    return e.iter().sum();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orient2d_simple() {
+        assert!(orient2d([0., 0.], [1., 0.], [0., 1.]) > 0.);
+        assert!(orient2d([0., 0.], [0., 1.], [1., 0.]) < 0.);
+        assert_eq!(orient2d([0., 0.], [1., 1.], [2., 2.]), 0.);
+    }
+
+    #[test]
+    fn orient2d_nearly_collinear() {
+        // naive evaluation of these points gives wrong signs
+        // (the classic example from Kettner et al., "Classroom Examples of Robustness Problems")
+        let a = [0.5, 0.5];
+        let b = [12., 12.];
+        let c = [24., 24.];
+        for i in 0..64 {
+            for j in 0..64 {
+                let p = [a[0] + i as f64 * f64::EPSILON, a[1] + j as f64 * f64::EPSILON];
+                let exact = orient2d(p, b, c);
+                // p.y - p.x decides on which side of the line y = x the point is
+                let expected = (j as f64 - i as f64).signum();
+                if i == j {
+                    assert_eq!(exact, 0.);
+                } else {
+                    assert_eq!(exact.signum(), expected, "i = {}, j = {}", i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn orient2d_antisymmetric() {
+        // b is one ulp above the line y = x through a and c
+        let a = [0.1, 0.1];
+        let b = [0.3, f64::from_bits(0.3f64.to_bits() + 1)];
+        let c = [0.7, 0.7];
+        assert!(orient2d(a, b, c) < 0.);
+        assert!(orient2d(b, a, c) > 0.);
+        assert!(orient2d(b, c, a) < 0.);
+        assert!(orient2d(a, c, b) > 0.);
+    }
+
+    #[test]
+    fn incircle_simple() {
+        let a = [1., 0.];
+        let b = [0., 1.];
+        let c = [-1., 0.];
+
+        assert!(incircle(a, b, c, [0., 0.]) > 0.);
+        assert!(incircle(a, b, c, [2., 2.]) < 0.);
+        assert_eq!(incircle(a, b, c, [0., -1.]), 0.);
+        assert!(incircle(c, b, a, [0., 0.]) < 0.);
+    }
+
+    #[test]
+    fn incircle_nearly_cocircular() {
+        let a = [1., 0.];
+        let b = [0., 1.];
+        let c = [-1., 0.];
+
+        assert!(incircle(a, b, c, [0., -1. + f64::EPSILON]) > 0.);
+        assert!(incircle(a, b, c, [0., -1. - f64::EPSILON]) < 0.);
+        assert!(incircle(a, b, c, [1e-20, -1.]) < 0.);
+    }
+
+    #[test]
+    fn expansions() {
+        let (x, y) = two_product(1. + f64::EPSILON, 1. + f64::EPSILON);
+        assert_eq!(x, 1. + 2. * f64::EPSILON);
+        assert_eq!(y, f64::EPSILON * f64::EPSILON);
+
+        let e = expansion_sum(&[1e-30], &[1e30, -1e30]);
+        assert_eq!(estimate(&e), 1e-30);
+    }
+}
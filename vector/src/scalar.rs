@@ -0,0 +1,192 @@
+use core::fmt::Debug;
+use core::ops;
+
+use crate::math::FloatMath;
+
+/**
+ * Number type used for coordinates, lengths and angles.
+ * Implemented for f32 and f64, so the same geometry code can run
+ * with single precision or with double precision for large worlds.
+ */
+pub trait Scalar:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+    + ops::Neg<Output = Self>
+    + ops::AddAssign
+    + ops::SubAssign
+    + ops::MulAssign
+    + ops::DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const PI: Self;
+    /**
+     * Difference between 1 and the next representable value
+     */
+    const EPSILON: Self;
+    /**
+     * Largest finite value, -MAX is the smallest one
+     */
+    const MAX: Self;
+
+    /**
+     * Converts a f32 constant (like 0.5 or 1e-6) into the scalar type
+     */
+    fn from_f32(v: f32) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    fn sqrt(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn floor(self) -> Self;
+
+    /**
+     * Number of representable values between self and other
+     */
+    fn ulps_between(self, other: Self) -> u64;
+
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+macro_rules! impl_float_scalar {
+    ($t:ident) => {
+        impl Scalar for $t {
+            const ZERO: Self = 0.;
+            const ONE: Self = 1.;
+            const PI: Self = core::$t::consts::PI;
+            const EPSILON: Self = $t::EPSILON;
+            const MAX: Self = $t::MAX;
+
+            fn from_f32(v: f32) -> Self {
+                return v as $t;
+            }
+
+            fn to_f64(self) -> f64 {
+                return self as f64;
+            }
+
+            fn sqrt(self) -> Self {
+                return FloatMath::sqrt(self);
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                return FloatMath::atan2(self, x);
+            }
+
+            fn sin(self) -> Self {
+                return FloatMath::sin(self);
+            }
+
+            fn cos(self) -> Self {
+                return FloatMath::cos(self);
+            }
+
+            fn floor(self) -> Self {
+                return FloatMath::floor(self);
+            }
+
+            fn ulps_between(self, other: Self) -> u64 {
+                if self == other {
+                    return 0;
+                }
+                if self.is_nan() || other.is_nan() || self.is_sign_negative() != other.is_sign_negative() {
+                    return u64::MAX;
+                }
+                return (self.to_bits() as i64 - other.to_bits() as i64).unsigned_abs();
+            }
+
+            fn abs(self) -> Self {
+                return FloatMath::abs(self);
+            }
+
+            fn min(self, other: Self) -> Self {
+                return $t::min(self, other);
+            }
+
+            fn max(self, other: Self) -> Self {
+                return $t::max(self, other);
+            }
+        }
+    };
+}
+
+impl_float_scalar!(f32);
+impl_float_scalar!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hypot<T: Scalar>(a: T, b: T) -> T {
+        return (a * a + b * b).sqrt();
+    }
+
+    #[test]
+    fn generic_math() {
+        assert_eq!(hypot(3f32, 4f32), 5f32);
+        assert_eq!(hypot(3f64, 4f64), 5f64);
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(Scalar::min(1f32, 2.), 1.);
+        assert_eq!(Scalar::max(1f64, 2.), 2.);
+        assert_eq!(Scalar::abs(-2f64), 2.);
+    }
+
+    fn lowest<T: Scalar>() -> T {
+        return -T::MAX;
+    }
+
+    #[test]
+    fn max_value() {
+        assert_eq!(lowest::<f32>(), f32::MIN);
+        assert_eq!(lowest::<f64>(), f64::MIN);
+    }
+
+    #[test]
+    fn from_f32() {
+        assert_eq!(f64::from_f32(0.5), 0.5);
+        assert_eq!(f32::from_f32(0.25), 0.25);
+    }
+
+    #[test]
+    fn ulps_between() {
+        assert_eq!(1f32.ulps_between(1.), 0);
+        assert_eq!(1f32.ulps_between(1. + f32::EPSILON), 1);
+        assert_eq!((-1f64).ulps_between(-1. - 2. * f64::EPSILON), 2);
+        assert_eq!(0f32.ulps_between(-0.), 0);
+        assert_eq!(1f32.ulps_between(-1.), u64::MAX);
+        assert_eq!(f64::NAN.ulps_between(f64::NAN), u64::MAX);
+    }
+}
@@ -0,0 +1,243 @@
+use core::ops;
+
+use crate::polar::Angle;
+use crate::scalar::Scalar;
+use crate::vector::Vector;
+
+/**
+ * Affine 2D transform stored as a 3x2 matrix:
+ *
+ * | x_axis.0  y_axis.0  translation.0 |
+ * | x_axis.1  y_axis.1  translation.1 |
+ *
+ * A point p is mapped to x_axis * p.0 + y_axis * p.1 + translation.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform2D<T = f32> {
+    pub x_axis: Vector<T>,
+    pub y_axis: Vector<T>,
+    pub translation: Vector<T>,
+}
+
+impl<T: Scalar> Default for Transform2D<T> {
+    fn default() -> Self {
+        return Transform2D::identity();
+    }
+}
+
+impl<T: Scalar> Transform2D<T> {
+    pub fn identity() -> Transform2D<T> {
+        return Transform2D {
+            x_axis: Vector(T::ONE, T::ZERO),
+            y_axis: Vector(T::ZERO, T::ONE),
+            translation: Vector(T::ZERO, T::ZERO),
+        };
+    }
+
+    pub fn from_translation(offset: Vector<T>) -> Transform2D<T> {
+        return Transform2D {
+            translation: offset,
+            ..Transform2D::identity()
+        };
+    }
+
+    /**
+     * Counter-clockwise rotation around the origin, angle in radians
+     */
+    pub fn from_rotation(angle: T) -> Transform2D<T> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        return Transform2D {
+            x_axis: Vector(cos, sin),
+            y_axis: Vector(-sin, cos),
+            translation: Vector(T::ZERO, T::ZERO),
+        };
+    }
+
+    pub fn from_scale(scale: Vector<T>) -> Transform2D<T> {
+        return Transform2D {
+            x_axis: Vector(scale.0, T::ZERO),
+            y_axis: Vector(T::ZERO, scale.1),
+            translation: Vector(T::ZERO, T::ZERO),
+        };
+    }
+
+    /**
+     * Scale first, then rotate, then translate
+     */
+    pub fn from_trs(translation: Vector<T>, rotation: T, scale: Vector<T>) -> Transform2D<T> {
+        let r = Transform2D::from_rotation(rotation);
+        return Transform2D {
+            x_axis: r.x_axis * scale.0,
+            y_axis: r.y_axis * scale.1,
+            translation,
+        };
+    }
+
+    /**
+     * Transform applying self first and then next
+     */
+    pub fn then(&self, next: &Transform2D<T>) -> Transform2D<T> {
+        return Transform2D {
+            x_axis: next.apply_direction(&self.x_axis),
+            y_axis: next.apply_direction(&self.y_axis),
+            translation: next.apply_point(&self.translation),
+        };
+    }
+
+    pub fn determinant(&self) -> T {
+        return self.x_axis.perp_dot(&self.y_axis);
+    }
+
+    /**
+     * None if the transform is degenerate (collapses the plane to a line or a point)
+     */
+    pub fn inverse(&self) -> Option<Transform2D<T>> {
+        let det = self.determinant();
+        if det == T::ZERO {
+            return None;
+        }
+
+        let x_axis = Vector(self.y_axis.1, -self.x_axis.1) / det;
+        let y_axis = Vector(-self.y_axis.0, self.x_axis.0) / det;
+        let translation = -(x_axis * self.translation.0 + y_axis * self.translation.1);
+
+        return Some(Transform2D {
+            x_axis,
+            y_axis,
+            translation,
+        });
+    }
+
+    pub fn apply_point(&self, p: &Vector<T>) -> Vector<T> {
+        return self.apply_direction(p) + self.translation;
+    }
+
+    /**
+     * Applies the linear part only, translation does not affect directions
+     */
+    pub fn apply_direction(&self, d: &Vector<T>) -> Vector<T> {
+        return self.x_axis * d.0 + self.y_axis * d.1;
+    }
+
+    /**
+     * Splits the transform into translation, rotation and scale.
+     * Shear is not representable and gets lost.
+     */
+    pub fn decompose(&self) -> (Vector<T>, T, Vector<T>) {
+        let rotation = self.x_axis.rotation();
+        let sx = self.x_axis.size();
+        let sy = if self.determinant() < T::ZERO {
+            -self.y_axis.size()
+        } else {
+            self.y_axis.size()
+        };
+        return (self.translation, rotation, Vector(sx, sy));
+    }
+
+    /**
+     * Interpolates translation, rotation (along the shorter arc) and scale separately
+     */
+    pub fn lerp(&self, other: &Transform2D<T>, t: T) -> Transform2D<T> {
+        let (t1, r1, s1) = self.decompose();
+        let (t2, r2, s2) = other.decompose();
+
+        let rotation = Angle(r1).lerp(&Angle(r2), t);
+
+        return Transform2D::from_trs(t1 + (t2 - t1) * t, rotation.radians(), s1 + (s2 - s1) * t);
+    }
+}
+
+/**
+ * a * b applies b first and then a, as with matrices
+ */
+impl<T: Scalar> ops::Mul for Transform2D<T> {
+    type Output = Transform2D<T>;
+
+    fn mul(self, b: Transform2D<T>) -> Transform2D<T> {
+        return b.then(&self);
+    }
+}
+
+impl<T: Scalar> ops::Mul<Vector<T>> for Transform2D<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, p: Vector<T>) -> Vector<T> {
+        return self.apply_point(&p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    fn assert_near(a: Vector, b: Vector) {
+        assert!((a - b).size() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn apply() {
+        let t = Transform2D::from_trs(Vector(10., 0.), FRAC_PI_2, Vector(2., 3.));
+
+        assert_near(t.apply_point(&Vector(1., 0.)), Vector(10., 2.));
+        assert_near(t.apply_point(&Vector(0., 1.)), Vector(7., 0.));
+        assert_near(t.apply_direction(&Vector(1., 0.)), Vector(0., 2.));
+        assert_near(t * Vector(0., 0.), Vector(10., 0.));
+    }
+
+    #[test]
+    fn compose() {
+        let rotate = Transform2D::from_rotation(FRAC_PI_2);
+        let shift = Transform2D::from_translation(Vector(1., 0.));
+        let p = Vector(1., 1.);
+
+        assert_near(rotate.then(&shift).apply_point(&p), Vector(0., 1.));
+        assert_near(shift.then(&rotate).apply_point(&p), Vector(-1., 2.));
+        assert_near((shift * rotate).apply_point(&p), Vector(0., 1.));
+    }
+
+    #[test]
+    fn inverse() {
+        let t = Transform2D::from_trs(Vector(3., -2.), 0.7, Vector(2., -0.5));
+        let inv = t.inverse().unwrap();
+        let p = Vector(4., 5.);
+
+        assert_near(inv.apply_point(&t.apply_point(&p)), p);
+        assert_near(t.then(&inv).apply_point(&p), p);
+        assert_eq!(Transform2D::from_scale(Vector(0., 1.)).inverse(), None);
+    }
+
+    #[test]
+    fn decompose() {
+        let (t, r, s) = Transform2D::from_trs(Vector(3., -2.), 0.7, Vector(2., -0.5)).decompose();
+
+        assert_near(t, Vector(3., -2.));
+        assert!((r - 0.7).abs() < 1e-5);
+        assert_near(s, Vector(2., -0.5));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Transform2D::from_trs(Vector(0., 0.), 0.9 * PI, Vector(1., 1.));
+        let b = Transform2D::from_trs(Vector(10., 0.), -0.9 * PI, Vector(3., 3.));
+        let (t, r, s) = a.lerp(&b, 0.5).decompose();
+
+        assert_near(t, Vector(5., 0.));
+        assert!((r.abs() - PI).abs() < 1e-5);
+        assert_near(s, Vector(2., 2.));
+        assert_near(a.lerp(&b, 0.).apply_point(&Vector(1., 2.)), a.apply_point(&Vector(1., 2.)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let t = Transform2D::from_trs(Vector(3., -2.), 0.7, Vector(2., -0.5));
+
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(serde_json::from_str::<Transform2D>(&json).unwrap(), t);
+
+        let ron = ron::to_string(&t).unwrap();
+        assert_eq!(ron::from_str::<Transform2D>(&ron).unwrap(), t);
+    }
+}
@@ -1,86 +1,139 @@
-use crate::polar::Polar;
+use core::iter::Sum;
+use core::ops;
 
-#[derive(PartialEq, Debug)]
+use crate::fixed::{Fix16, Fix32};
+use crate::polar::{Angle, Polar};
+use crate::predicates;
+use crate::scalar::Scalar;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Orientation {
     Collinear,
     Clockwise,
     Counterclockwise,
 }
 
-#[derive(Default, Clone, Debug, PartialEq)]
-pub struct Vector(pub f32, pub f32);
+impl Orientation {
+    /**
+     * Classifies a signed turn value (like perp_dot or orient2d),
+     * values within [-epsilon, epsilon] are treated as collinear
+     */
+    pub fn classify<T: Scalar>(value: T, epsilon: T) -> Orientation {
+        if value > epsilon {
+            Orientation::Counterclockwise
+        } else if value < -epsilon {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector<T = f32>(pub T, pub T);
 
-impl Vector {
-    pub fn size(&self) -> f32 {
+impl<T: Scalar> Vector<T> {
+    pub fn size(&self) -> T {
         return (self.0 * self.0 + self.1 * self.1).sqrt();
     }
 
-    pub fn rotation(&self) -> f32 {
+    pub fn rotation(&self) -> T {
         return self.1.atan2(self.0);
     }
 
-    pub fn add(&mut self, a: &Vector) -> &mut Self {
+    pub fn add(&mut self, a: &Vector<T>) -> &mut Self {
         self.0 += a.0;
         self.1 += a.1;
         return self;
     }
 
-    pub fn sub(&mut self, a: &Vector) -> &mut Self {
+    pub fn sub(&mut self, a: &Vector<T>) -> &mut Self {
         self.0 -= a.0;
         self.1 -= a.1;
         return self;
     }
 
-    pub fn clone_sub(&self, a: &Vector) -> Vector {
-        let mut result = self.clone();
-        result.sub(a);
-        return result;
+    pub fn clone_sub(&self, a: &Vector<T>) -> Vector<T> {
+        return *self - *a;
     }
 
-    pub fn clone_add(&self, a: &Vector) -> Vector {
-        let mut result = self.clone();
-        result.add(a);
-        return result;
+    pub fn clone_add(&self, a: &Vector<T>) -> Vector<T> {
+        return *self + *a;
     }
 
-    pub fn clone_scale(&self, s: f32) -> Vector {
-        let mut result = self.clone();
-        result.scale(s);
-        return result;
+    pub fn clone_scale(&self, s: T) -> Vector<T> {
+        return *self * s;
     }
 
 
     pub fn invert(&mut self) -> &mut Self {
-        self.0 *= -1.0;
-        self.1 *= -1.0;
+        self.0 = -self.0;
+        self.1 = -self.1;
         return self;
     }
 
-    pub fn as_polar(&mut self) -> Polar {
+    pub fn as_polar(&mut self) -> Polar<T> {
         return Polar {
             length: self.size(),
-            rotation: self.rotation(),
+            rotation: Angle(self.rotation()),
         };
     }
 
     pub fn normalize(&mut self) -> &mut Self {
-        if self.size() > 0. {
-            self.scale(1.0 / self.size());
+        if self.size() > T::ZERO {
+            self.scale(T::ONE / self.size());
         }
         return self;
     }
 
-    pub fn scale(&mut self, s: f32) -> &mut Self {
+    pub fn scale(&mut self, s: T) -> &mut Self {
         self.0 *= s;
         self.1 *= s;
         return self;
     }
 
-    // pub fn orientation(p: &Vector, q: &Vector, r: &Vector) -> Orientation {
-    //     let pq = p.clone_sub(q);
-    //     let qr = p.clone_sub(r);
-    //     return pq.cross(&qr);
-    // }
+    /**
+     * Exact orientation of the turn p -> q -> r,
+     * computed with the robust orient2d predicate
+     */
+    pub fn orientation(p: &Vector<T>, q: &Vector<T>, r: &Vector<T>) -> Orientation {
+        return Orientation::classify(Vector::orient2d(p, q, r), 0.);
+    }
+
+    /**
+     * Orientation of the turn p -> q -> r where nearly collinear points count as collinear.
+     * epsilon is the sine of the largest angle between pq and pr still treated as collinear.
+     */
+    pub fn orientation_eps(p: &Vector<T>, q: &Vector<T>, r: &Vector<T>, epsilon: T) -> Orientation {
+        let pq = *q - *p;
+        let pr = *r - *p;
+        return Orientation::classify(pq.perp_dot(&pr), epsilon * pq.size() * pr.size());
+    }
+
+    /**
+     * Twice the signed area of triangle (p, q, r): positive for counter-clockwise,
+     * negative for clockwise and exactly zero for collinear points.
+     * Uses adaptive-precision arithmetic on f64, so the sign is never wrong for f32, f64 and
+     * Fix16. Fix32 converts exactly only below 2^21 (raw values below 2^53), larger
+     * coordinates are rounded before the test.
+     */
+    pub fn orient2d(p: &Vector<T>, q: &Vector<T>, r: &Vector<T>) -> f64 {
+        return predicates::orient2d(p.to_f64(), q.to_f64(), r.to_f64());
+    }
+
+    /**
+     * Positive if d lies inside the circle through a, b, c (given counter-clockwise),
+     * negative if outside and exactly zero if the points are cocircular.
+     * Exact for the same scalars and ranges as orient2d.
+     */
+    pub fn incircle(a: &Vector<T>, b: &Vector<T>, c: &Vector<T>, d: &Vector<T>) -> f64 {
+        return predicates::incircle(a.to_f64(), b.to_f64(), c.to_f64(), d.to_f64());
+    }
+
+    fn to_f64(self) -> [f64; 2] {
+        return [self.0.to_f64(), self.1.to_f64()];
+    }
 
     /**
        The dot product is useful for many things:
@@ -89,25 +142,255 @@ impl Vector {
        If A • B < 0: vectors point in opposite directions
        If A • B = 0: vectors are perpendicular
     */
-    pub fn dot(&self, d: &Vector) -> f32 {
+    pub fn dot(&self, d: &Vector<T>) -> T {
         self.0 * d.0 + self.1 * d.1
     }
     /**
-     * Given vectors a and b:
-        cross(a, b) > 0: b is to the left of a (counter-clockwise turn).
-        cross(a, b) < 0: b is to the right of a (clockwise turn).
-        cross(a, b) = 0: a and b are collinear (parallel or same line).
+     * Signed 2D cross product (perp dot product) of vectors a and b:
+     * perp_dot(a, b) > 0: b is to the left of a (counter-clockwise turn).
+     * perp_dot(a, b) < 0: b is to the right of a (clockwise turn).
+     * perp_dot(a, b) = 0: a and b are collinear (parallel or same line).
      */
-    pub fn cross(&self, d: &Vector)  -> Orientation {
-        let val = self.0 * d.1 - self.1 * d.0;
+    pub fn perp_dot(&self, d: &Vector<T>) -> T {
+        self.0 * d.1 - self.1 * d.0
+    }
 
-        if val > 0.0 {
-            Orientation::Clockwise
-        } else if val < 0.0 {
-            Orientation::Counterclockwise
-        } else {
-            Orientation::Collinear
+    /**
+     * Turn direction from vector a to vector b, see perp_dot
+     */
+    pub fn cross(&self, d: &Vector<T>)  -> Orientation {
+        return Orientation::classify(self.perp_dot(d), T::ZERO);
+    }
+
+    pub fn size_squared(&self) -> T {
+        return self.dot(self);
+    }
+
+    pub fn distance(&self, p: &Vector<T>) -> T {
+        return (*p - *self).size();
+    }
+
+    pub fn distance_squared(&self, p: &Vector<T>) -> T {
+        return (*p - *self).size_squared();
+    }
+
+    /**
+     * Counter-clockwise rotation, angle in radians
+     */
+    pub fn rotate(&self, angle: T) -> Vector<T> {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        return Vector(self.0 * cos - self.1 * sin, self.0 * sin + self.1 * cos);
+    }
+
+    /**
+     * Counter-clockwise rotation of a point around pivot, angle in radians
+     */
+    pub fn rotate_around(&self, pivot: &Vector<T>, angle: T) -> Vector<T> {
+        return (*self - *pivot).rotate(angle) + *pivot;
+    }
+
+    /**
+     * Perpendicular vector of the same size, rotated by 90° counter-clockwise
+     */
+    pub fn perp_left(&self) -> Vector<T> {
+        return Vector(-self.1, self.0);
+    }
+
+    /**
+     * Perpendicular vector of the same size, rotated by 90° clockwise
+     */
+    pub fn perp_right(&self) -> Vector<T> {
+        return Vector(self.1, -self.0);
+    }
+
+    /**
+     * Mirrors the vector about the line with the given normal,
+     * e.g. bounces a velocity off a wall. The normal does not have to be unit length.
+     */
+    pub fn reflect(&self, normal: &Vector<T>) -> Vector<T> {
+        let nn = normal.size_squared();
+        if nn == T::ZERO {
+            return *self;
         }
+        return *self - *normal * (T::from_f32(2.) * self.dot(normal) / nn);
+    }
+
+    /**
+     * Component of the vector parallel to onto
+     */
+    pub fn project_onto(&self, onto: &Vector<T>) -> Vector<T> {
+        let oo = onto.size_squared();
+        if oo == T::ZERO {
+            return Vector(T::ZERO, T::ZERO);
+        }
+        return *onto * (self.dot(onto) / oo);
+    }
+
+    /**
+     * Component of the vector perpendicular to from
+     */
+    pub fn reject_from(&self, from: &Vector<T>) -> Vector<T> {
+        return *self - self.project_onto(from);
+    }
+
+    /**
+     * Signed angle in radians to rotate self onto d,
+     * positive for counter-clockwise, in range [-PI, PI]
+     */
+    pub fn angle_between(&self, d: &Vector<T>) -> T {
+        return self.perp_dot(d).atan2(self.dot(d));
+    }
+
+    /**
+     * Same direction, but no longer than max
+     */
+    pub fn clamp_length(&self, max: T) -> Vector<T> {
+        let size = self.size();
+        if size > max && size > T::ZERO {
+            return *self * (max / size);
+        }
+        return *self;
+    }
+
+    /**
+     * Linear interpolation, t = 0 gives self and t = 1 gives d
+     */
+    pub fn lerp(&self, d: &Vector<T>, t: T) -> Vector<T> {
+        return *self + (*d - *self) * t;
+    }
+
+    /**
+     * Moves towards target by at most max_delta without overshooting it
+     */
+    pub fn move_towards(&self, target: &Vector<T>, max_delta: T) -> Vector<T> {
+        let delta = *target - *self;
+        let distance = delta.size();
+        if distance <= max_delta || distance == T::ZERO {
+            return *target;
+        }
+        return *self + delta * (max_delta / distance);
+    }
+}
+
+impl<T: Scalar> ops::Add for Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, a: Vector<T>) -> Vector<T> {
+        return Vector(self.0 + a.0, self.1 + a.1);
+    }
+}
+
+impl<T: Scalar> ops::Sub for Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, a: Vector<T>) -> Vector<T> {
+        return Vector(self.0 - a.0, self.1 - a.1);
+    }
+}
+
+impl<T: Scalar> ops::Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        return Vector(-self.0, -self.1);
+    }
+}
+
+impl<T: Scalar> ops::Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, s: T) -> Vector<T> {
+        return Vector(self.0 * s, self.1 * s);
+    }
+}
+
+macro_rules! impl_scalar_mul_vector {
+    ($t:ty) => {
+        impl ops::Mul<Vector<$t>> for $t {
+            type Output = Vector<$t>;
+
+            fn mul(self, v: Vector<$t>) -> Vector<$t> {
+                return v * self;
+            }
+        }
+    };
+}
+
+impl_scalar_mul_vector!(f32);
+impl_scalar_mul_vector!(f64);
+impl_scalar_mul_vector!(Fix16);
+impl_scalar_mul_vector!(Fix32);
+
+impl<T: Scalar> ops::Div<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn div(self, s: T) -> Vector<T> {
+        return Vector(self.0 / s, self.1 / s);
+    }
+}
+
+impl<T: Scalar> ops::AddAssign for Vector<T> {
+    fn add_assign(&mut self, a: Vector<T>) {
+        self.0 += a.0;
+        self.1 += a.1;
+    }
+}
+
+impl<T: Scalar> ops::SubAssign for Vector<T> {
+    fn sub_assign(&mut self, a: Vector<T>) {
+        self.0 -= a.0;
+        self.1 -= a.1;
+    }
+}
+
+impl<T: Scalar> ops::MulAssign<T> for Vector<T> {
+    fn mul_assign(&mut self, s: T) {
+        self.0 *= s;
+        self.1 *= s;
+    }
+}
+
+impl<T: Scalar> ops::DivAssign<T> for Vector<T> {
+    fn div_assign(&mut self, s: T) {
+        self.0 /= s;
+        self.1 /= s;
+    }
+}
+
+impl<T: Scalar> Sum for Vector<T> {
+    fn sum<I: Iterator<Item = Vector<T>>>(iter: I) -> Vector<T> {
+        return iter.fold(Vector::default(), |acc, v| acc + v);
+    }
+}
+
+impl<'a, T: Scalar> Sum<&'a Vector<T>> for Vector<T> {
+    fn sum<I: Iterator<Item = &'a Vector<T>>>(iter: I) -> Vector<T> {
+        return iter.fold(Vector::default(), |acc, v| acc + *v);
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from(t: (T, T)) -> Vector<T> {
+        return Vector(t.0, t.1);
+    }
+}
+
+impl<T> From<[T; 2]> for Vector<T> {
+    fn from(a: [T; 2]) -> Vector<T> {
+        let [x, y] = a;
+        return Vector(x, y);
+    }
+}
+
+impl<T> From<Vector<T>> for (T, T) {
+    fn from(v: Vector<T>) -> (T, T) {
+        return (v.0, v.1);
+    }
+}
+
+impl<T> From<Vector<T>> for [T; 2] {
+    fn from(v: Vector<T>) -> [T; 2] {
+        return [v.0, v.1];
     }
 }
 
@@ -117,14 +400,14 @@ mod tests {
 
     #[test]
     fn default() {
-        let v = Vector::default();
+        let v: Vector = Vector::default();
         assert_eq!(v.0, 0.);
         assert_eq!(v.1, 0.);
     }
 
     #[test]
     fn size() {
-        let v1 = Vector::default();
+        let v1: Vector = Vector::default();
         assert_eq!(v1.size(), 0.);
         let v2 = Vector(3., 4.);
         assert_eq!(v2.size(), 5.);
@@ -180,8 +463,254 @@ mod tests {
         let b2 = Vector(1., 0.5);
         let b3 = Vector(10., 10.);
 
-        assert_eq!(a.cross(&b1), Orientation::Clockwise);
-        assert_eq!(a.cross(&b2), Orientation::Counterclockwise);
+        assert_eq!(a.cross(&b1), Orientation::Counterclockwise);
+        assert_eq!(a.cross(&b2), Orientation::Clockwise);
         assert_eq!(a.cross(&b3), Orientation::Collinear);
     }
+
+    #[test]
+    fn perp_dot() {
+        let a = Vector(1., 1.);
+
+        assert_eq!(a.perp_dot(&Vector(1., 4.)), 3.);
+        assert_eq!(a.perp_dot(&Vector(1., 0.5)), -0.5);
+        assert_eq!(a.perp_dot(&Vector(10., 10.)), 0.);
+    }
+
+    #[test]
+    fn orientation() {
+        let p = Vector(0., 0.);
+        let q = Vector(1., 0.);
+
+        assert_eq!(Vector::orientation(&p, &q, &Vector(0., 1.)), Orientation::Counterclockwise);
+        assert_eq!(Vector::orientation(&p, &q, &Vector(0., -1.)), Orientation::Clockwise);
+        assert_eq!(Vector::orientation(&p, &q, &Vector(2., 0.)), Orientation::Collinear);
+    }
+
+    #[test]
+    fn orientation_nearly_collinear() {
+        let p: Vector<f32> = Vector(0.5, 0.5);
+        let q = Vector(12., 12.);
+        let r = Vector(24., 24.);
+        let above = Vector(0.5, 0.5 + f32::EPSILON);
+        let below = Vector(0.5 + f32::EPSILON, 0.5);
+
+        assert_eq!(Vector::orientation(&p, &q, &r), Orientation::Collinear);
+        assert_eq!(Vector::orientation(&above, &q, &r), Orientation::Counterclockwise);
+        assert_eq!(Vector::orientation(&below, &q, &r), Orientation::Clockwise);
+
+        assert_eq!(Vector::orientation_eps(&q, &r, &above, 1e-3), Orientation::Collinear);
+        assert_eq!(Vector::orientation_eps(&q, &r, &Vector(0., 1.), 1e-3), Orientation::Counterclockwise);
+    }
+
+    #[test]
+    fn orient2d_fix32() {
+        // nearly collinear triples with raw values below 2^53, checked against i128 arithmetic
+        let mut state = 7u64;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            return (state >> 12) as i64 - (1 << 51);
+        };
+        for _ in 0..1000 {
+            let (p, q) = ((next(), next()), (next() >> 1, next() >> 1));
+            let r = (2 * q.0 - p.0 + (next() >> 48), 2 * q.1 - p.1 + (next() >> 48));
+            let exact = (q.0 - p.0) as i128 * (r.1 - p.1) as i128 - (q.1 - p.1) as i128 * (r.0 - p.0) as i128;
+
+            let fixed = |v: (i64, i64)| Vector(Fix32::from_raw(v.0), Fix32::from_raw(v.1));
+            let sign = Vector::orient2d(&fixed(p), &fixed(q), &fixed(r));
+            assert_eq!(sign.partial_cmp(&0.), exact.partial_cmp(&0), "{:?} {:?} {:?}", p, q, r);
+        }
+    }
+
+    #[test]
+    fn incircle() {
+        let a = Vector(1., 0.);
+        let b = Vector(0., 1.);
+        let c = Vector(-1., 0.);
+
+        assert!(Vector::incircle(&a, &b, &c, &Vector(0., 0.)) > 0.);
+        assert!(Vector::incircle(&a, &b, &c, &Vector(0., -2.)) < 0.);
+        assert_eq!(Vector::incircle(&a, &b, &c, &Vector(0., -1.)), 0.);
+    }
+
+    #[test]
+    fn operators() {
+        let a = Vector(1., 2.);
+        let b = Vector(3., 4.);
+
+        assert_eq!(a + b, Vector(4., 6.));
+        assert_eq!(b - a, Vector(2., 2.));
+        assert_eq!(-a, Vector(-1., -2.));
+        assert_eq!(a * 2., Vector(2., 4.));
+        assert_eq!(2. * a, Vector(2., 4.));
+        assert_eq!(b / 2., Vector(1.5, 2.));
+    }
+
+    #[test]
+    fn assign_operators() {
+        let mut v = Vector(1., 2.);
+        v += Vector(1., 1.);
+        assert_eq!(v, Vector(2., 3.));
+        v -= Vector(2., 2.);
+        assert_eq!(v, Vector(0., 1.));
+        v *= 4.;
+        assert_eq!(v, Vector(0., 4.));
+        v /= 2.;
+        assert_eq!(v, Vector(0., 2.));
+    }
+
+    #[test]
+    fn sum() {
+        let vs = [Vector(1., 2.), Vector(3., 4.), Vector(-1., 0.)];
+
+        assert_eq!(vs.iter().sum::<Vector>(), Vector(3., 6.));
+        assert_eq!(vs.into_iter().sum::<Vector>(), Vector(3., 6.));
+        assert_eq!(Vec::<Vector>::new().into_iter().sum::<Vector>(), Vector(0., 0.));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Vector::from((1., 2.)), Vector(1., 2.));
+        assert_eq!(Vector::from([3., 4.]), Vector(3., 4.));
+
+        let t: (f32, f32) = Vector(5., 6.).into();
+        let a: [f32; 2] = Vector(7., 8.).into();
+        assert_eq!(t, (5., 6.));
+        assert_eq!(a, [7., 8.]);
+    }
+
+    #[test]
+    fn clone_ops() {
+        let a = Vector(1., 2.);
+        let b = Vector(3., 4.);
+
+        assert_eq!(a.clone_add(&b), Vector(4., 6.));
+        assert_eq!(a.clone_sub(&b), Vector(-2., -2.));
+        assert_eq!(a.clone_scale(3.), Vector(3., 6.));
+    }
+
+    #[test]
+    fn double_precision() {
+        let a: Vector<f64> = Vector(1e9, 3.);
+        let b = Vector(1e9, 4.);
+
+        assert_eq!(b - a, Vector(0., 1.));
+        assert_eq!(Vector(3f64, 4.).size(), 5.);
+        assert_eq!(2f64 * Vector(1., 2.), Vector(2., 4.));
+    }
+
+    fn assert_near(a: Vector, b: Vector) {
+        assert!((a - b).size() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn distance() {
+        let a = Vector(1., 1.);
+        let b = Vector(4., 5.);
+
+        assert_eq!(a.distance(&b), 5.);
+        assert_eq!(a.distance_squared(&b), 25.);
+        assert_eq!(b.size_squared(), 41.);
+    }
+
+    #[test]
+    fn rotate() {
+        let v = Vector(1., 0.);
+
+        assert_near(v.rotate(std::f32::consts::FRAC_PI_2), Vector(0., 1.));
+        assert_near(v.rotate(std::f32::consts::PI), Vector(-1., 0.));
+        assert_near(v.rotate(-std::f32::consts::FRAC_PI_2), Vector(0., -1.));
+    }
+
+    #[test]
+    fn rotate_around() {
+        let v = Vector(2., 1.);
+
+        assert_near(v.rotate_around(&Vector(1., 1.), std::f32::consts::FRAC_PI_2), Vector(1., 2.));
+        assert_near(v.rotate_around(&v, 1.), v);
+    }
+
+    #[test]
+    fn perp() {
+        let v = Vector(2., 1.);
+
+        assert_eq!(v.perp_left(), Vector(-1., 2.));
+        assert_eq!(v.perp_right(), Vector(1., -2.));
+        assert_eq!(v.dot(&v.perp_left()), 0.);
+        assert!(v.perp_dot(&v.perp_left()) > 0.);
+    }
+
+    #[test]
+    fn reflect() {
+        let v = Vector(1., -1.);
+
+        assert_eq!(v.reflect(&Vector(0., 1.)), Vector(1., 1.));
+        assert_eq!(v.reflect(&Vector(0., 5.)), Vector(1., 1.));
+        assert_eq!(v.reflect(&Vector(-1., 0.)), Vector(-1., -1.));
+        assert_eq!(v.reflect(&Vector(0., 0.)), v);
+    }
+
+    #[test]
+    fn project_and_reject() {
+        let v = Vector(3., 4.);
+        let onto = Vector(2., 0.);
+
+        assert_eq!(v.project_onto(&onto), Vector(3., 0.));
+        assert_eq!(v.reject_from(&onto), Vector(0., 4.));
+        assert_eq!(v.project_onto(&Vector(0., 0.)), Vector(0., 0.));
+        assert_eq!(v.project_onto(&onto) + v.reject_from(&onto), v);
+    }
+
+    #[test]
+    fn angle_between() {
+        let x = Vector(1., 0.);
+
+        assert!((x.angle_between(&Vector(0., 2.)) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((x.angle_between(&Vector(0., -2.)) + std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((x.angle_between(&Vector(-1., 0.)).abs() - std::f32::consts::PI).abs() < 1e-6);
+        assert_eq!(x.angle_between(&Vector(3., 0.)), 0.);
+    }
+
+    #[test]
+    fn clamp_length() {
+        let v = Vector(3., 4.);
+
+        assert_eq!(v.clamp_length(10.), v);
+        assert_eq!(v.clamp_length(2.5), Vector(1.5, 2.));
+        assert_eq!(Vector(0., 0.).clamp_length(0.), Vector(0., 0.));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Vector(0., 10.);
+        let b = Vector(10., 0.);
+
+        assert_eq!(a.lerp(&b, 0.), a);
+        assert_eq!(a.lerp(&b, 1.), b);
+        assert_eq!(a.lerp(&b, 0.25), Vector(2.5, 7.5));
+    }
+
+    #[test]
+    fn move_towards() {
+        let a = Vector(0., 0.);
+        let b = Vector(3., 4.);
+
+        assert_near(a.move_towards(&b, 1.), Vector(0.6, 0.8));
+        assert_eq!(a.move_towards(&b, 5.), b);
+        assert_eq!(a.move_towards(&b, 100.), b);
+        assert_eq!(b.move_towards(&b, 1.), b);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let v = Vector(1.5f32, -2.);
+
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, "[1.5,-2.0]");
+        assert_eq!(serde_json::from_str::<Vector>(&json).unwrap(), v);
+
+        let ron = ron::to_string(&v).unwrap();
+        assert_eq!(ron::from_str::<Vector>(&ron).unwrap(), v);
+    }
 }